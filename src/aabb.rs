@@ -0,0 +1,299 @@
+//! Axis-aligned bounding boxes
+
+use crate::{
+    vector::Vector, line::{Line, closest}, segment::Segment, ray::{self, Ray}, triangle::Triangle,
+    sphere::Sphere, tetrahedron::Tetrahedron, math::tolerance,
+    plain::{Plain, side::Side, line_relations::PlainLineRelations}
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Aabb {
+    pub min: Vector,
+    pub max: Vector
+}
+
+/// The relation between a box and a plain
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AabbPlainRelations {
+    /// The box is on the side the plumb of the plain points to, possibly touching the plain
+    Front,
+    /// The box is on the side opposite to the plumb, possibly touching the plain
+    Back,
+    /// The plain passes through the interior of the box, or the box lies in the plain
+    Straddling
+}
+
+/// The coordinates of a vector, indexed by axis
+fn coordinates(vector: &Vector) -> [f64; 3] {
+    [vector.0, vector.1, vector.2]
+}
+
+impl Aabb {
+
+    /// Generates a box from its corners
+    /// # Panics:
+    /// - If a coordinate of the minimal corner is larger than the same coordinate of the maximal one
+    pub fn new(min: Vector, max: Vector) -> Aabb {
+        if min.0 > max.0 || min.1 > max.1 || min.2 > max.2 {
+            panic!("The minimal corner of a box must not exceed its maximal corner")
+        }
+        Aabb { min, max }
+    }
+
+    /// Generates the smallest box containing all of the given points, if any are given
+    pub fn from_points(points: &[Vector]) -> Option<Aabb> {
+        let first = *points.first()?;
+        Some(points.iter().fold(Aabb { min: first, max: first }, |aabb, point| aabb.extended_to(point)))
+    }
+
+    /// The smallest box containing this one and a given point
+    pub fn extended_to(&self, point: &Vector) -> Aabb {
+        Aabb {
+            min: Vector(self.min.0.min(point.0), self.min.1.min(point.1), self.min.2.min(point.2)),
+            max: Vector(self.max.0.max(point.0), self.max.1.max(point.1), self.max.2.max(point.2))
+        }
+    }
+
+    pub fn center(&self) -> Vector {
+        0.5 * (self.min + self.max)
+    }
+
+    /// The lengths of the sides of the box along the x, y and z axes
+    pub fn size(&self) -> Vector {
+        self.max - self.min
+    }
+
+    /// The eight corners of the box
+    pub fn corners(&self) -> [Vector; 8] {
+        let (min, max) = (self.min, self.max);
+        [
+            Vector(min.0, min.1, min.2), Vector(max.0, min.1, min.2), Vector(max.0, max.1, min.2), Vector(min.0, max.1, min.2),
+            Vector(min.0, min.1, max.2), Vector(max.0, min.1, max.2), Vector(max.0, max.1, max.2), Vector(min.0, max.1, max.2)
+        ]
+    }
+
+    /// The twelve edges of the box
+    pub fn edges(&self) -> [Segment; 12] {
+        let c = self.corners();
+        [
+            Segment::new(c[0], c[1]), Segment::new(c[1], c[2]), Segment::new(c[2], c[3]), Segment::new(c[3], c[0]),
+            Segment::new(c[4], c[5]), Segment::new(c[5], c[6]), Segment::new(c[6], c[7]), Segment::new(c[7], c[4]),
+            Segment::new(c[0], c[4]), Segment::new(c[1], c[5]), Segment::new(c[2], c[6]), Segment::new(c[3], c[7])
+        ]
+    }
+
+    /// The smallest box containing two boxes
+    pub fn union(aabb1: &Aabb, aabb2: &Aabb) -> Aabb {
+        aabb1.extended_to(&aabb2.min).extended_to(&aabb2.max)
+    }
+
+    /// The common box of two boxes, if they overlap
+    pub fn intersection(aabb1: &Aabb, aabb2: &Aabb) -> Option<Aabb> {
+        let min = Vector(aabb1.min.0.max(aabb2.min.0), aabb1.min.1.max(aabb2.min.1), aabb1.min.2.max(aabb2.min.2));
+        let max = Vector(aabb1.max.0.min(aabb2.max.0), aabb1.max.1.min(aabb2.max.1), aabb1.max.2.min(aabb2.max.2));
+        if min.0 > max.0 || min.1 > max.1 || min.2 > max.2 { None } else { Some(Aabb { min, max }) }
+    }
+
+    /// Check if a point is inside the box or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        let (min, max, point) = (coordinates(&self.min), coordinates(&self.max), coordinates(point));
+        (0..3).all(|axis| point[axis] > min[axis] - tolerance::EPSILON && point[axis] < max[axis] + tolerance::EPSILON)
+    }
+
+    /// Check if another box is entirely inside this one
+    pub fn contains(&self, other: &Aabb) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Find the parameters where a line enters and exits the box, using the slab method,
+    /// with the parameters restricted to the given bounds
+    fn slab_parameters(&self, line: &Line, bounds: (f64, f64)) -> Option<(f64, f64)> {
+        let (min, max) = (coordinates(&self.min), coordinates(&self.max));
+        let (origin, direction) = (coordinates(&line.point), coordinates(&line.direction));
+        let (mut entry, mut exit) = bounds;
+        for axis in 0..3 {
+            if tolerance::is_zero(direction[axis]) {
+                // parallel to the slab, either always inside it or never
+                if origin[axis] < min[axis] - tolerance::EPSILON || origin[axis] > max[axis] + tolerance::EPSILON {
+                    return None;
+                }
+                continue;
+            }
+            let t1 = (min[axis] - origin[axis]) / direction[axis];
+            let t2 = (max[axis] - origin[axis]) / direction[axis];
+            entry = entry.max(t1.min(t2));
+            exit = exit.min(t1.max(t2));
+        }
+        if entry > exit + tolerance::EPSILON { None } else { Some((entry, exit)) }
+    }
+
+    /// Find the parameters of the points where a line enters and exits the box, if it crosses it
+    pub fn intersection_with_line(&self, line: &Line) -> Option<(f64, f64)> {
+        self.slab_parameters(line, closest::UNBOUNDED)
+    }
+
+    /// Find the parameters of the points where a ray enters and exits the box, if it crosses it.
+    /// A ray starting inside the box enters it at parameter 0
+    pub fn intersection_with_ray(&self, ray: &Ray) -> Option<(f64, f64)> {
+        self.slab_parameters(&ray.line(), ray::BOUNDS)
+    }
+
+    /// Compute the relation between the box and a plain
+    pub fn relation_with_plain(&self, plain: &Plain) -> AabbPlainRelations {
+        let sides = plain.classify(&self.corners());
+        let (has_front, has_back) = (sides.contains(&Side::Front), sides.contains(&Side::Back));
+        match (has_front, has_back) {
+            (true, false) => AabbPlainRelations::Front,
+            (false, true) => AabbPlainRelations::Back,
+            _ => AabbPlainRelations::Straddling
+        }
+    }
+
+    /// Find the polygon a plain cuts out of the box, as its vertices in order around its boundary.
+    /// Returns an empty polygon if the plain misses the box, and fewer than three vertices if it only touches it
+    pub fn section_by(&self, plain: &Plain) -> Vec<Vector> {
+        let mut vertices: Vec<Vector> = vec![];
+        let mut add_vertex = |point: Vector| {
+            if !vertices.iter().any(|vertex| tolerance::is_zero((*vertex - point).length())) {
+                vertices.push(point);
+            }
+        };
+        for edge in self.edges() {
+            match edge.relation_with_plain(plain) {
+                Some(PlainLineRelations::Intersect(point, _)) => add_vertex(point),
+                Some(PlainLineRelations::Containing) => {
+                    add_vertex(edge.start);
+                    add_vertex(edge.end);
+                },
+                _ => ()
+            }
+        }
+        if vertices.len() < 3 {
+            return vertices;
+        }
+
+        // a section of a convex solid is convex, so sort the vertices by their angle around the center
+        let center = (1.0 / vertices.len() as f64) * vertices.iter().fold(Vector(0.0, 0.0, 0.0), |sum, vertex| sum + *vertex);
+        let basis = plain.basis_at(&center);
+        let angle = |vertex: &Vector| { let (u, v) = basis.coordinates_of(vertex); v.atan2(u) };
+        vertices.sort_by(|vertex1, vertex2| angle(vertex1).total_cmp(&angle(vertex2)));
+        vertices
+    }
+}
+
+impl From<&Segment> for Aabb {
+    fn from(segment: &Segment) -> Self {
+        Aabb::from_points(&[segment.start, segment.end]).unwrap()
+    }
+}
+
+impl From<&Triangle> for Aabb {
+    fn from(triangle: &Triangle) -> Self {
+        Aabb::from_points(&[triangle.a, triangle.b, triangle.c]).unwrap()
+    }
+}
+
+impl From<&Tetrahedron> for Aabb {
+    fn from(tetrahedron: &Tetrahedron) -> Self {
+        Aabb::from_points(&tetrahedron.vertices).unwrap()
+    }
+}
+
+impl From<&Sphere> for Aabb {
+    fn from(sphere: &Sphere) -> Self {
+        let radius = Vector(sphere.radius, sphere.radius, sphere.radius);
+        Aabb::new(sphere.center - radius, sphere.center + radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit_cube() -> Aabb {
+        Aabb::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn creates_from_points() {
+        let aabb = Aabb::from_points(&[Vector(1.0, -2.0, 3.0), Vector(-1.0, 4.0, 0.0), Vector(0.0, 0.0, 5.0)]).unwrap();
+        assert_eq!(aabb, Aabb::new(Vector(-1.0, -2.0, 0.0), Vector(1.0, 4.0, 5.0)));
+        assert!(Aabb::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn creates_from_primitives() {
+        let sphere = Sphere::new(Vector(1.0, 1.0, 1.0), 2.0);
+        assert_eq!(Aabb::from(&sphere), Aabb::new(Vector(-1.0, -1.0, -1.0), Vector(3.0, 3.0, 3.0)));
+        let triangle = Triangle::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 1.0), Vector(0.0, -3.0, 0.0));
+        assert_eq!(Aabb::from(&triangle), Aabb::new(Vector(0.0, -3.0, 0.0), Vector(2.0, 0.0, 1.0)));
+        let segment = Segment::new(Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0));
+        assert_eq!(Aabb::from(&segment), Aabb::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let other = Aabb::new(Vector(0.5, 0.5, 0.5), Vector(2.0, 2.0, 2.0));
+        assert_eq!(Aabb::union(&unit_cube(), &other), Aabb::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 2.0, 2.0)));
+        assert_eq!(Aabb::intersection(&unit_cube(), &other).unwrap(), Aabb::new(Vector(0.5, 0.5, 0.5), Vector(1.0, 1.0, 1.0)));
+        let far = Aabb::new(Vector(3.0, 3.0, 3.0), Vector(4.0, 4.0, 4.0));
+        assert!(Aabb::intersection(&unit_cube(), &far).is_none());
+    }
+
+    #[test]
+    fn containment() {
+        let cube = unit_cube();
+        assert!(cube.contains_point(&Vector(0.5, 1.0, 0.0)));
+        assert!(!cube.contains_point(&Vector(0.5, 1.5, 0.0)));
+        assert!(cube.contains(&Aabb::new(Vector(0.2, 0.2, 0.2), Vector(0.8, 0.8, 0.8))));
+        assert!(!cube.contains(&Aabb::new(Vector(0.2, 0.2, 0.2), Vector(1.8, 0.8, 0.8))));
+    }
+
+    #[test]
+    fn slab_intersection() {
+        let cube = unit_cube();
+        let line = Line::new(Vector(-1.0, 0.5, 0.5), Vector(1.0, 0.0, 0.0));
+        assert_eq!(cube.intersection_with_line(&line), Some((1.0, 2.0)));
+        let diagonal = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 1.0));
+        assert_eq!(cube.intersection_with_line(&diagonal), Some((0.0, 1.0)));
+        let missing = Line::new(Vector(-1.0, 2.0, 0.5), Vector(1.0, 0.0, 0.0));
+        assert!(cube.intersection_with_line(&missing).is_none());
+
+        let inside = Ray::new(Vector(0.5, 0.5, 0.5), Vector(0.0, 0.0, 1.0));
+        assert_eq!(cube.intersection_with_ray(&inside), Some((0.0, 0.5)));
+        let away = Ray::new(Vector(0.5, 0.5, 2.0), Vector(0.0, 0.0, 1.0));
+        assert!(cube.intersection_with_ray(&away).is_none());
+    }
+
+    #[test]
+    fn plain_relation() {
+        let cube = unit_cube();
+        let below = Plain::new(&Vector(0.0, 0.0, -1.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=-1
+        assert_eq!(cube.relation_with_plain(&below), AabbPlainRelations::Front);
+        assert_eq!(cube.relation_with_plain(&below.flipped()), AabbPlainRelations::Back);
+        let touching = Plain::new(&Vector(0.0, 0.0, 1.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=1
+        assert_eq!(cube.relation_with_plain(&touching), AabbPlainRelations::Back);
+        let middle = Plain::new(&Vector(0.0, 0.0, 0.5), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0.5
+        assert_eq!(cube.relation_with_plain(&middle), AabbPlainRelations::Straddling);
+    }
+
+    #[test]
+    fn plain_section() {
+        let cube = unit_cube();
+        let middle = Plain::new(&Vector(0.0, 0.0, 0.5), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0.5
+        let square = cube.section_by(&middle);
+        assert_eq!(square.len(), 4);
+        assert_eq!(Vector::parallelogram_area_of_points(&square[0], &square[1], &square[2]), 1.0);
+
+        // x + y + z = 1.5 cuts a regular hexagon
+        let diagonal = Plain::from_three_points(&Vector(1.5, 0.0, 0.0), &Vector(0.0, 1.5, 0.0), &Vector(0.0, 0.0, 1.5));
+        let hexagon = cube.section_by(&diagonal);
+        assert_eq!(hexagon.len(), 6);
+        let side = (hexagon[0] - hexagon[5]).length();
+        assert!(hexagon.windows(2).all(|pair| ((pair[1] - pair[0]).length() - side).abs() < 0.00001));
+
+        let corner = Plain::from_three_points(&Vector(1.0, 1.0, 1.0), &Vector(2.0, 1.0, 0.0), &Vector(1.0, 2.0, 0.0));
+        assert_eq!(cube.section_by(&corner), vec![Vector(1.0, 1.0, 1.0)]);
+        assert!(cube.section_by(&Plain::new(&Vector(0.0, 0.0, 3.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0))).is_empty());
+    }
+}
@@ -0,0 +1,204 @@
+//! Affine transformations of space: a linear map followed by a translation
+
+use std::ops::Mul;
+
+use crate::{vector::Vector, matrix::Matrix3, line::Line, plain::Plain, math::tolerance};
+
+/// The affine transformation `x -> linear * x + translation`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Affine3 {
+    pub linear: Matrix3,
+    pub translation: Vector
+}
+
+impl Affine3 {
+
+    pub fn new(linear: Matrix3, translation: Vector) -> Affine3 {
+        Affine3 { linear, translation }
+    }
+
+    /// The transformation leaving every point in place
+    pub fn identity() -> Affine3 {
+        Affine3::from_linear(Matrix3::identity())
+    }
+
+    /// A linear transformation, which keeps the origin in place
+    pub fn from_linear(linear: Matrix3) -> Affine3 {
+        Affine3::new(linear, Vector(0.0, 0.0, 0.0))
+    }
+
+    /// Move every point by the same offset
+    pub fn translation(offset: &Vector) -> Affine3 {
+        Affine3::new(Matrix3::identity(), *offset)
+    }
+
+    /// Scale each axis separately about the origin
+    pub fn scaling(factors: &Vector) -> Affine3 {
+        Affine3::from_linear(Matrix3::diagonal(factors.0, factors.1, factors.2))
+    }
+
+    /// Rotate by an angle about an axis through the origin, counterclockwise when looking against the axis
+    /// # Panics:
+    /// - If the axis is the zero vector
+    pub fn rotation(axis: &Vector, angle: f64) -> Affine3 {
+        Affine3::from_linear(Matrix3::rotation(axis, angle))
+    }
+
+    /// Rotate by an angle about an axis line, counterclockwise when looking against its direction
+    /// # Panics:
+    /// - If the direction of the axis is the zero vector
+    pub fn rotation_about(axis: &Line, angle: f64) -> Affine3 {
+        // move the axis to the origin, rotate, and move it back
+        let linear = Matrix3::rotation(&axis.direction, angle);
+        Affine3::new(linear, axis.point - linear * axis.point)
+    }
+
+    /// Shear along a direction, moving each point proportionally to its signed distance from the plain through the origin
+    /// perpendicular to a normal: `x -> x + factor * (n * x) * direction`, with a unit normal `n`
+    /// # Panics:
+    /// - If the normal is the zero vector, or is not perpendicular to the direction
+    pub fn shear(direction: &Vector, normal: &Vector, factor: f64) -> Affine3 {
+        if normal.length() == 0.0 {
+            panic!("Cannot shear relative to the zero normal")
+        }
+        let unit = (1.0 / normal.length()) * *normal;
+        if !tolerance::is_zero(unit * *direction) {
+            panic!("A shear direction must be parallel to the fixed plain")
+        }
+        Affine3::from_linear(Matrix3::identity() + factor * Matrix3::outer_product(direction, &unit))
+    }
+
+    /// Reflect every point through a plain
+    pub fn reflection(plain: &Plain) -> Affine3 {
+        // x -> x - 2n(n * (x - q)), for the unit normal n and a point q on the plain
+        let normal = plain.unit_normal();
+        let point = plain.projection_of(&Vector(0.0, 0.0, 0.0));
+        Affine3::new(
+            Matrix3::identity() - 2.0 * Matrix3::outer_product(&normal, &normal),
+            (2.0 * (normal * point)) * normal
+        )
+    }
+
+    /// The transformation undoing this one. Fails if the linear part is singular
+    pub fn inverse(&self) -> Result<Affine3, ()> {
+        let linear = self.linear.inverse()?;
+        Ok(Affine3::new(linear, -(linear * self.translation)))
+    }
+
+    /// Apply the transformation to a point
+    pub fn apply_to_point(&self, point: &Vector) -> Vector {
+        self.linear * *point + self.translation
+    }
+
+    /// Apply the transformation to a direction, which is not affected by the translation
+    pub fn apply_to_direction(&self, direction: &Vector) -> Vector {
+        self.linear * *direction
+    }
+
+    /// Apply the transformation to a line.
+    /// A singular transformation may map it to a line with a zero direction
+    pub fn apply_to_line(&self, line: &Line) -> Line {
+        Line::new(self.apply_to_point(&line.point), self.apply_to_direction(&line.direction))
+    }
+
+    /// Apply the transformation to a plain, keeping the side its plumb points to.
+    /// Fails if the transformation is singular, flattening the plain
+    pub fn apply_to_plain(&self, plain: &Plain) -> Result<Plain, ()> {
+        // the plumb is orthogonal to all directions in the plain, so it maps by the inverse-transpose
+        let plumb = self.linear.inverse()?.transpose() * plain.unit_normal();
+        let point = self.apply_to_point(&plain.projection_of(&Vector(0.0, 0.0, 0.0)));
+        Ok(Plain::from_plumb(&point, &plumb))
+    }
+}
+
+impl Mul for Affine3 {
+    type Output = Self;
+
+    /// Compose two transformations, applying the right one first
+    fn mul(self, rhs: Self) -> Self::Output {
+        Affine3::new(self.linear * rhs.linear, self.linear * rhs.translation + self.translation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close};
+
+    #[test]
+    fn transforms_points_and_directions() {
+        let translation = Affine3::translation(&Vector(1.0, 2.0, 3.0));
+        assert_close(&translation.apply_to_point(&Vector(1.0, 1.0, 1.0)), &Vector(2.0, 3.0, 4.0));
+        assert_close(&translation.apply_to_direction(&Vector(1.0, 1.0, 1.0)), &Vector(1.0, 1.0, 1.0));
+
+        let scaling = Affine3::scaling(&Vector(2.0, 3.0, -1.0));
+        assert_close(&scaling.apply_to_point(&Vector(1.0, 1.0, 1.0)), &Vector(2.0, 3.0, -1.0));
+
+        let rotation = Affine3::rotation(&Vector(0.0, 0.0, 1.0), PI / 2.0);
+        assert_close(&rotation.apply_to_point(&Vector(1.0, 0.0, 5.0)), &Vector(0.0, 1.0, 5.0));
+
+        let shear = Affine3::shear(&Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 2.0), 0.5);
+        assert_close(&shear.apply_to_point(&Vector(0.0, 1.0, 4.0)), &Vector(2.0, 1.0, 4.0));
+        assert!((shear.linear.determinant() - 1.0).abs() < EPSILON);
+
+        let reflection = Affine3::reflection(&Plain::from_plumb(&Vector(0.0, 0.0, 1.0), &Vector(0.0, 0.0, 3.0)));
+        assert_close(&reflection.apply_to_point(&Vector(1.0, 2.0, 3.0)), &Vector(1.0, 2.0, -1.0));
+        assert_close(&(reflection * reflection).apply_to_point(&Vector(1.0, 2.0, 3.0)), &Vector(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn rotates_about_axis_line() {
+        let axis = Line::new(Vector(1.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        let rotation = Affine3::rotation_about(&axis, PI / 2.0);
+        assert_close(&rotation.apply_to_point(&Vector(2.0, 1.0, 3.0)), &Vector(1.0, 2.0, 3.0));
+        assert_close(&rotation.apply_to_point(&Vector(1.0, 1.0, 7.0)), &Vector(1.0, 1.0, 7.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn shear_along_normal() {
+        Affine3::shear(&Vector(0.0, 0.0, 1.0), &Vector(0.0, 1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn composes_and_inverts() {
+        let rotation = Affine3::rotation(&Vector(1.0, 1.0, 0.0), 0.7);
+        let translation = Affine3::translation(&Vector(1.0, -2.0, 0.5));
+        let scaling = Affine3::scaling(&Vector(2.0, 1.0, 0.5));
+        let transform = translation * rotation * scaling;
+        let point = Vector(0.3, -1.2, 2.0);
+        let expected = translation.apply_to_point(&rotation.apply_to_point(&scaling.apply_to_point(&point)));
+        assert_close(&transform.apply_to_point(&point), &expected);
+
+        let inverse = transform.inverse().unwrap();
+        assert_close(&inverse.apply_to_point(&expected), &point);
+        assert_close(&(transform * inverse).apply_to_point(&point), &point);
+
+        assert_eq!(Affine3::scaling(&Vector(1.0, 0.0, 1.0)).inverse(), Err(()));
+    }
+
+    #[test]
+    fn transforms_lines_and_plains() {
+        let transform = Affine3::translation(&Vector(0.0, 0.0, 1.0)) * Affine3::shear(&Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), 2.0)
+            * Affine3::scaling(&Vector(1.0, 3.0, 1.0));
+        let points = [Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0)];
+        let plain = Plain::from_three_points(&points[0], &points[1], &points[2]);
+        let image = transform.apply_to_plain(&plain).unwrap();
+        for point in &points {
+            assert!(image.contains_point(&transform.apply_to_point(point)));
+        }
+        // the side of every point is preserved
+        let outside = Vector(2.0, 2.0, 2.0);
+        assert_eq!(image.side_of(&transform.apply_to_point(&outside)), plain.side_of(&outside));
+
+        let line = Line::from_two_points(points[0], &points[1]);
+        let line_image = transform.apply_to_line(&line);
+        assert!(line_image.is_on_line(&transform.apply_to_point(&points[0])));
+        assert!(line_image.is_on_line(&transform.apply_to_point(&points[1])));
+        assert!(image.contains_line(&line_image));
+
+        assert_eq!(Affine3::scaling(&Vector(1.0, 1.0, 0.0)).apply_to_plain(&plain).map(|_| ()), Err(()));
+    }
+}
@@ -0,0 +1,323 @@
+//! Binary space partitioning trees of solids bounded by polygons
+
+use crate::{vector::Vector, polygon::Polygon, polyhedron::ConvexPolyhedron, plain::{Plain, side::Side}};
+
+/// The location of a point relative to the solid a tree bounds
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Location {
+    Inside,
+    Outside,
+    /// The point is on the boundary between the inside and the outside
+    Boundary
+}
+
+/// A binary space partitioning tree. The solid it bounds is behind its splitting plains:
+/// the plumbs of the plains, and the normals of the polygons, point out of the solid
+#[derive(Debug, Clone, Default)]
+pub struct BspTree {
+    node: Option<Box<BspNode>>
+}
+
+#[derive(Debug, Clone)]
+struct BspNode {
+    plain: Plain,
+    /// The polygons lying in the splitting plain
+    polygons: Vec<Polygon>,
+    front: BspTree,
+    back: BspTree
+}
+
+/// The parts of a polygon on each side of a plain, with parts lying in the plain kept apart,
+/// as (coplanar and facing the same way, coplanar and facing the other way, front, back)
+type SplitPolygon = (Option<Polygon>, Option<Polygon>, Option<Polygon>, Option<Polygon>);
+
+fn split_polygon(plain: &Plain, polygon: &Polygon) -> SplitPolygon {
+    let coplanar = polygon.vertices().iter().all(|vertex| plain.side_of(vertex) == Side::On);
+    if !coplanar {
+        let (front, back) = polygon.split(plain);
+        return (None, None, front, back);
+    }
+    if polygon.plain().unit_normal() * plain.unit_normal() > 0.0 {
+        (Some(polygon.clone()), None, None, None)
+    } else {
+        (None, Some(polygon.clone()), None, None)
+    }
+}
+
+impl BspTree {
+
+    /// Generates a tree from the polygons bounding a solid, with their normals pointing out of it
+    pub fn from_polygons(polygons: Vec<Polygon>) -> BspTree {
+        let mut tree = BspTree::default();
+        tree.build(polygons);
+        tree
+    }
+
+    /// Generates a tree from the faces of a convex polyhedron
+    pub fn from_polyhedron(polyhedron: &ConvexPolyhedron) -> BspTree {
+        let faces = (0..polyhedron.faces.len())
+            .map(|face| Polygon::new(polyhedron.face_polygon(face)))
+            .collect();
+        BspTree::from_polygons(faces)
+    }
+
+    /// Generates a tree splitting space by a sequence of plains, each one behind the previous ones.
+    /// It bounds the convex region behind all of the plains, and holds no polygons
+    pub fn from_plains(plains: &[Plain]) -> BspTree {
+        plains.iter().rev().fold(BspTree::default(), |back, plain| BspTree {
+            node: Some(Box::new(BspNode { plain: *plain, polygons: vec![], front: BspTree::default(), back }))
+        })
+    }
+
+    /// Add polygons to the tree, splitting them by the plains already in it
+    pub fn build(&mut self, polygons: Vec<Polygon>) {
+        if polygons.is_empty() {
+            return;
+        }
+        let node = self.node.get_or_insert_with(|| Box::new(BspNode {
+            plain: *polygons[0].plain(),
+            polygons: vec![],
+            front: BspTree::default(),
+            back: BspTree::default()
+        }));
+        let (mut front, mut back) = (vec![], vec![]);
+        for polygon in &polygons {
+            let (same, opposite, front_part, back_part) = split_polygon(&node.plain, polygon);
+            node.polygons.extend(same.into_iter().chain(opposite));
+            front.extend(front_part);
+            back.extend(back_part);
+        }
+        node.front.build(front);
+        node.back.build(back);
+    }
+
+    /// All polygons in the tree
+    pub fn polygons(&self) -> Vec<Polygon> {
+        match &self.node {
+            None => vec![],
+            Some(node) => {
+                let mut polygons = node.polygons.clone();
+                polygons.extend(node.front.polygons());
+                polygons.extend(node.back.polygons());
+                polygons
+            }
+        }
+    }
+
+    /// The volume of the solid bounded by the polygons in the tree
+    pub fn volume(&self) -> f64 {
+        // the signed volumes of the cones from the origin over each polygon
+        let origin = Vector(0.0, 0.0, 0.0);
+        self.polygons().iter()
+            .map(|polygon| {
+                let vertices = polygon.vertices();
+                (1..vertices.len() - 1)
+                    .map(|i| Vector::tetrahedron_volume_of_points(&origin, &vertices[0], &vertices[i], &vertices[i + 1]))
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+
+    /// Locate a point relative to the solid the tree bounds
+    pub fn locate(&self, point: &Vector) -> Location {
+        self.locate_in(point, Location::Outside)
+    }
+
+    /// Locate a point in this subtree, where an empty subtree is entirely at a given location
+    fn locate_in(&self, point: &Vector, location: Location) -> Location {
+        match &self.node {
+            None => location,
+            Some(node) => match node.plain.side_of(point) {
+                Side::Front => node.front.locate_in(point, Location::Outside),
+                Side::Back => node.back.locate_in(point, Location::Inside),
+                Side::On => {
+                    let (front, back) = (node.front.locate_in(point, Location::Outside), node.back.locate_in(point, Location::Inside));
+                    if front == back { front } else { Location::Boundary }
+                }
+            }
+        }
+    }
+
+    /// Split polygons into their fragments outside of the solid the tree bounds and inside it.
+    /// Fragments on the boundary are outside if they face the same way as it, and inside otherwise
+    pub fn split_polygons(&self, polygons: Vec<Polygon>) -> (Vec<Polygon>, Vec<Polygon>) {
+        self.split_in(polygons, Location::Outside)
+    }
+
+    fn split_in(&self, polygons: Vec<Polygon>, location: Location) -> (Vec<Polygon>, Vec<Polygon>) {
+        let node = match &self.node {
+            None if location == Location::Outside => return (polygons, vec![]),
+            None => return (vec![], polygons),
+            Some(node) => node
+        };
+        let (mut front, mut back) = (vec![], vec![]);
+        for polygon in &polygons {
+            let (same, opposite, front_part, back_part) = split_polygon(&node.plain, polygon);
+            front.extend(same.into_iter().chain(front_part));
+            back.extend(opposite.into_iter().chain(back_part));
+        }
+        let (mut outside, mut inside) = node.front.split_in(front, Location::Outside);
+        let (back_outside, back_inside) = node.back.split_in(back, Location::Inside);
+        outside.extend(back_outside);
+        inside.extend(back_inside);
+        (outside, inside)
+    }
+
+    /// The polygons in the order a painter draws them when looking from a viewpoint: from the farthest to the nearest
+    pub fn painter_order(&self, viewpoint: &Vector) -> Vec<&Polygon> {
+        match &self.node {
+            None => vec![],
+            Some(node) => {
+                let (far, near) = if node.plain.side_of(viewpoint) == Side::Back { (&node.front, &node.back) } else { (&node.back, &node.front) };
+                let mut polygons = far.painter_order(viewpoint);
+                polygons.extend(node.polygons.iter());
+                polygons.extend(near.painter_order(viewpoint));
+                polygons
+            }
+        }
+    }
+
+    /// Swap the inside and the outside of the solid
+    pub fn invert(&mut self) {
+        if let Some(node) = &mut self.node {
+            node.plain = node.plain.flipped();
+            node.polygons = node.polygons.iter().map(|polygon| polygon.reversed()).collect();
+            node.front.invert();
+            node.back.invert();
+            std::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    /// Remove the parts of the polygons in this tree that are inside the solid of another tree
+    fn clip_to(&mut self, other: &BspTree) {
+        if let Some(node) = &mut self.node {
+            node.polygons = other.split_polygons(std::mem::take(&mut node.polygons)).0;
+            node.front.clip_to(other);
+            node.back.clip_to(other);
+        }
+    }
+
+    /// The union of the solids of two trees
+    pub fn union(tree1: &BspTree, tree2: &BspTree) -> BspTree {
+        let (mut a, mut b) = (tree1.clone(), tree2.clone());
+        a.clip_to(&b);
+        b.clip_to(&a);
+        // remove the coplanar boundary parts that both trees keep
+        b.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.build(b.polygons());
+        a
+    }
+
+    /// The intersection of the solids of two trees
+    pub fn intersection(tree1: &BspTree, tree2: &BspTree) -> BspTree {
+        let (mut a, mut b) = (tree1.clone(), tree2.clone());
+        a.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.clip_to(&b);
+        b.clip_to(&a);
+        a.build(b.polygons());
+        a.invert();
+        a
+    }
+
+    /// The solid of the first tree, without the solid of the second
+    pub fn difference(tree1: &BspTree, tree2: &BspTree) -> BspTree {
+        let (mut a, mut b) = (tree1.clone(), tree2.clone());
+        a.invert();
+        a.clip_to(&b);
+        b.clip_to(&a);
+        b.invert();
+        b.clip_to(&a);
+        b.invert();
+        a.build(b.polygons());
+        a.invert();
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{plain::half_space::HalfSpace, polyhedron::HalfSpacesIntersection};
+
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// The tree of the box [min, min + size]^3
+    fn cube(min: f64, size: f64) -> BspTree {
+        let (low, high) = (Vector(min, min, min), Vector(min + size, min + size, min + size));
+        let axes = [Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0)];
+        let half_spaces: Vec<HalfSpace> = axes.iter()
+            .flat_map(|axis| [HalfSpace::behind(Plain::from_plumb(&high, axis)), HalfSpace::behind(Plain::from_plumb(&low, &(-*axis)))])
+            .collect();
+        match HalfSpacesIntersection::of(&half_spaces) {
+            HalfSpacesIntersection::Bounded(polyhedron) => BspTree::from_polyhedron(&polyhedron),
+            other => panic!("Expected a bounded cube, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn locates_points() {
+        let tree = cube(0.0, 2.0);
+        assert_eq!(tree.locate(&Vector(1.0, 1.0, 1.0)), Location::Inside);
+        assert_eq!(tree.locate(&Vector(1.0, 3.0, 1.0)), Location::Outside);
+        assert_eq!(tree.locate(&Vector(1.0, 2.0, 1.0)), Location::Boundary);
+        assert!((tree.volume() - 8.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn locates_points_with_plain_splitters() {
+        let tree = BspTree::from_plains(&[
+            Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 0.0, -1.0)),
+            Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &Vector(0.0, -1.0, 0.0)),
+            Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &Vector(-1.0, 0.0, 0.0)),
+        ]);
+        assert_eq!(tree.locate(&Vector(1.0, 1.0, 1.0)), Location::Inside);
+        assert_eq!(tree.locate(&Vector(1.0, -1.0, 1.0)), Location::Outside);
+        assert!(tree.polygons().is_empty());
+    }
+
+    #[test]
+    fn splits_polygons() {
+        let tree = cube(0.0, 2.0);
+        let crossing = Polygon::new(vec![Vector(-1.0, -1.0, 1.0), Vector(3.0, -1.0, 1.0), Vector(3.0, 3.0, 1.0), Vector(-1.0, 3.0, 1.0)]);
+        let (outside, inside) = tree.split_polygons(vec![crossing]);
+        let area = |polygons: &[Polygon]| polygons.iter().map(|polygon| polygon.area()).sum::<f64>();
+        assert!((area(&inside) - 4.0).abs() < EPSILON);
+        assert!((area(&outside) - 12.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn paints_from_far_to_near() {
+        let tree = cube(0.0, 2.0);
+        let viewpoint = Vector(1.0, 1.0, 10.0);
+        let order = tree.painter_order(&viewpoint);
+        assert_eq!(order.len(), 6);
+        // the top face is the only one facing the viewpoint, so it is drawn over all the others
+        assert!((order.last().unwrap().plain().unit_normal() - Vector(0.0, 0.0, 1.0)).length() < EPSILON);
+
+        let beside = Vector(10.0, 1.0, 1.0);
+        let order = tree.painter_order(&beside);
+        assert!((order.last().unwrap().plain().unit_normal() - Vector(1.0, 0.0, 0.0)).length() < EPSILON);
+    }
+
+    #[test]
+    fn constructive_solid_geometry() {
+        let (a, b) = (cube(0.0, 2.0), cube(1.0, 2.0));
+        let union = BspTree::union(&a, &b);
+        assert!((union.volume() - 15.0).abs() < EPSILON);
+        assert_eq!(union.locate(&Vector(2.5, 2.5, 2.5)), Location::Inside);
+
+        let intersection = BspTree::intersection(&a, &b);
+        assert!((intersection.volume() - 1.0).abs() < EPSILON);
+        assert_eq!(intersection.locate(&Vector(0.5, 0.5, 0.5)), Location::Outside);
+        assert_eq!(intersection.locate(&Vector(1.5, 1.5, 1.5)), Location::Inside);
+
+        let difference = BspTree::difference(&a, &b);
+        assert!((difference.volume() - 7.0).abs() < EPSILON);
+        assert_eq!(difference.locate(&Vector(1.5, 1.5, 1.5)), Location::Outside);
+        assert_eq!(difference.locate(&Vector(0.5, 0.5, 0.5)), Location::Inside);
+    }
+}
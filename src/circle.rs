@@ -0,0 +1,211 @@
+//! A module for circles in space
+
+use std::f64::consts::PI;
+
+use crate::{
+    vector::Vector, line::Line, triangle::Triangle, math::tolerance,
+    plain::{Plain, line_relations::PlainLineRelations},
+    sphere::{Sphere, line_relations::SphereLineRelations, plain_relations::SpherePlainRelations}
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Circle3 {
+    pub center: Vector,
+    pub radius: f64,
+    /// The plain the circle lies in
+    pub plain: Plain
+}
+
+impl Circle3 {
+
+    pub fn new(center: Vector, radius: f64, plain: Plain) -> Circle3 {
+        Circle3 { center, radius, plain }
+    }
+
+    /// Generates the circle passing through three points
+    /// # Panics:
+    /// - If the three points are collinear
+    pub fn from_three_points(point1: &Vector, point2: &Vector, point3: &Vector) -> Circle3 {
+        let triangle = Triangle::new(*point1, *point2, *point3);
+        let center = triangle.circumcenter();
+        Circle3::new(center, (*point1 - center).length(), triangle.plain())
+    }
+
+    /// Generates the circle a plain cuts out of a sphere, if the plain cuts it
+    pub fn section_of(sphere: &Sphere, plain: &Plain) -> Option<Circle3> {
+        match SpherePlainRelations::of(sphere, plain) {
+            SpherePlainRelations::Intersect(center, radius) => Some(Circle3::new(center, radius, *plain)),
+            _ => None
+        }
+    }
+
+    /// The sphere with the same center and radius, containing the circle
+    fn sphere(&self) -> Sphere {
+        Sphere::new(self.center, self.radius)
+    }
+
+    /// Check if a point is on the circle
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        self.plain.contains_point(point) && self.sphere().is_on_surface(point)
+    }
+
+    /// Find the points where a line meets the circle (0, 1 or 2 points)
+    pub fn intersection_with_line(&self, line: &Line) -> Vec<Vector> {
+        match self.plain.relation_with_line(line) {
+            PlainLineRelations::Containing => match SphereLineRelations::of(&self.sphere(), line) {
+                SphereLineRelations::Apart(_) => vec![],
+                SphereLineRelations::Tangent(point) => vec![point],
+                SphereLineRelations::Secant(point1, point2) => vec![point1, point2]
+            },
+            PlainLineRelations::Intersect(point, _) if self.contains_point(&point) => vec![point],
+            _ => vec![]
+        }
+    }
+
+    /// Find the points where a plain meets the circle (0, 1 or 2 points)
+    /// # Panics:
+    /// - If the plain contains the circle
+    pub fn intersection_with_plain(&self, plain: &Plain) -> Vec<Vector> {
+        if are_parallel(&self.plain, plain) {
+            if plain.contains_point(&self.center) {
+                panic!("The plain contains the circle, and meets it at infinitely many points")
+            }
+            return vec![];
+        }
+        self.intersection_with_line(&Plain::intersection_line_of(&self.plain, plain))
+    }
+
+    /// Find the points where two circles meet (0, 1 or 2 points)
+    /// # Panics:
+    /// - If the two circles unite
+    pub fn intersection(circle1: &Circle3, circle2: &Circle3) -> Vec<Vector> {
+        let coplanar = are_parallel(&circle1.plain, &circle2.plain)
+            && circle1.plain.contains_point(&circle2.center);
+        if !coplanar {
+            // the common points are where the first circle meets the plain of the second
+            return circle1.intersection_with_plain(&circle2.plain).into_iter()
+                .filter(|point| circle2.contains_point(point))
+                .collect();
+        }
+
+        let centers = circle2.center - circle1.center;
+        let distance = centers.length();
+        let (r1, r2) = (circle1.radius, circle2.radius);
+        if tolerance::is_zero(distance) {
+            if tolerance::approx_eq(r1, r2) {
+                panic!("The circles unite, and meet at infinitely many points")
+            }
+            return vec![];
+        }
+        // the common chord is perpendicular to the line of the centers, at distance a from the first center
+        let a = (distance * distance + r1 * r1 - r2 * r2) / (2.0 * distance);
+        let chord = Line::new(
+            circle1.center + (a / distance) * centers,
+            Vector::vectoric_product(&circle1.plain.unit_normal(), &centers)
+        );
+        circle1.intersection_with_line(&chord)
+    }
+
+    /// Sample points evenly spaced along the circle, starting from an arbitrary point
+    pub fn sample(&self, count: usize) -> Vec<Vector> {
+        let basis = self.plain.basis_at(&self.center);
+        (0..count)
+            .map(|i| {
+                let angle = 2.0 * PI * (i as f64) / (count as f64);
+                basis.point_at(self.radius * angle.cos(), self.radius * angle.sin())
+            })
+            .collect()
+    }
+}
+
+/// Check if two plains are parallel, up to the tolerance on the sine of the angle between them
+fn are_parallel(plain1: &Plain, plain2: &Plain) -> bool {
+    tolerance::is_zero(Vector::vectoric_product(&plain1.unit_normal(), &plain2.unit_normal()).length())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close, xy_plain};
+
+    fn unit_circle() -> Circle3 {
+        Circle3::new(Vector(0.0, 0.0, 0.0), 1.0, xy_plain())
+    }
+
+    #[test]
+    fn creates_from_three_points() {
+        let circle = Circle3::from_three_points(&Vector(1.0, 0.0, 2.0), &Vector(0.0, 1.0, 2.0), &Vector(-1.0, 0.0, 2.0));
+        assert_close(&circle.center, &Vector(0.0, 0.0, 2.0));
+        assert_eq!(circle.radius, 1.0);
+        assert!(circle.contains_point(&Vector(0.0, -1.0, 2.0)));
+        assert!(!circle.contains_point(&Vector(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn creates_from_sphere_section() {
+        let sphere = Sphere::new(Vector(0.0, 0.0, 3.0), 5.0);
+        let circle = Circle3::section_of(&sphere, &xy_plain()).unwrap();
+        assert_eq!((circle.center, circle.radius), (Vector(0.0, 0.0, 0.0), 4.0));
+        assert!(Circle3::section_of(&Sphere::new(Vector(0.0, 0.0, 3.0), 1.0), &xy_plain()).is_none());
+    }
+
+    #[test]
+    fn intersection_with_plain() {
+        let circle = unit_circle();
+        let plain = Plain::new(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)); // y=0
+        let points = circle.intersection_with_plain(&plain);
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().all(|point| circle.contains_point(point) && plain.contains_point(point)));
+
+        let tangent = Plain::new(&Vector(0.0, 1.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)); // y=1
+        assert_eq!(circle.intersection_with_plain(&tangent), vec![Vector(0.0, 1.0, 0.0)]);
+        let parallel = Plain::new(&Vector(0.0, 0.0, 1.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=1
+        assert!(circle.intersection_with_plain(&parallel).is_empty());
+        // off parallel only by rounding, so it still misses the circle rather than meeting it far away
+        let nearly_parallel = Plain::from_plumb(&Vector(0.0, 0.0, 1.0), &Vector(1e-12, 0.0, 1.0));
+        assert!(circle.intersection_with_plain(&nearly_parallel).is_empty());
+    }
+
+    #[test]
+    fn intersection_with_line() {
+        let circle = unit_circle();
+        let secant = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(circle.intersection_with_line(&secant), vec![Vector(-1.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)]);
+        let piercing = Line::new(Vector(0.0, 1.0, 5.0), Vector(0.0, 0.0, 1.0));
+        assert_eq!(circle.intersection_with_line(&piercing), vec![Vector(0.0, 1.0, 0.0)]);
+        let missing = Line::new(Vector(0.0, 0.5, 5.0), Vector(0.0, 0.0, 1.0));
+        assert!(circle.intersection_with_line(&missing).is_empty());
+    }
+
+    #[test]
+    fn intersection_of_coplanar_circles() {
+        let circle1 = Circle3::new(Vector(0.0, 0.0, 0.0), 5.0, xy_plain());
+        let circle2 = Circle3::new(Vector(6.0, 0.0, 0.0), 5.0, xy_plain());
+        let points = Circle3::intersection(&circle1, &circle2);
+        assert_eq!(points.len(), 2);
+        assert_close(&points[0], &Vector(3.0, -4.0, 0.0));
+        assert_close(&points[1], &Vector(3.0, 4.0, 0.0));
+
+        let touching = Circle3::new(Vector(10.0, 0.0, 0.0), 5.0, xy_plain());
+        assert_eq!(Circle3::intersection(&circle1, &touching), vec![Vector(5.0, 0.0, 0.0)]);
+        let apart = Circle3::new(Vector(20.0, 0.0, 0.0), 5.0, xy_plain());
+        assert!(Circle3::intersection(&circle1, &apart).is_empty());
+    }
+
+    #[test]
+    fn intersection_of_circles_in_space() {
+        let circle1 = unit_circle();
+        let y_plain = Plain::new(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)); // y=0
+        let circle2 = Circle3::new(Vector(1.0, 0.0, 1.0), 1.0, y_plain);
+        assert_eq!(Circle3::intersection(&circle1, &circle2), vec![Vector(1.0, 0.0, 0.0)]);
+    }
+
+    #[test]
+    fn samples_points() {
+        let circle = Circle3::new(Vector(1.0, 2.0, 3.0), 2.0, Plain::from_three_points(&Vector(1.0, 2.0, 3.0), &Vector(2.0, 2.0, 4.0), &Vector(1.0, 3.0, 3.0)));
+        let points = circle.sample(8);
+        assert_eq!(points.len(), 8);
+        assert!(points.iter().all(|point| circle.contains_point(point)));
+        assert!(((points[0] - points[1]).length() - (points[1] - points[2]).length()).abs() < EPSILON);
+    }
+}
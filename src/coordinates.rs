@@ -0,0 +1,98 @@
+//! Spherical and cylindrical coordinates of points, relative to the origin and the z axis
+
+use std::f64::consts::PI;
+
+use crate::vector::Vector;
+
+/// Spherical coordinates: the distance from the origin, the polar angle from the positive z axis (0 <= x <= PI),
+/// and the azimuth around the z axis from the positive x axis (-PI < x <= PI)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spherical {
+    pub radius: f64,
+    pub polar: f64,
+    pub azimuth: f64
+}
+
+/// Cylindrical coordinates: the distance from the z axis, the azimuth around it
+/// from the positive x axis (-PI < x <= PI), and the height along it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cylindrical {
+    pub radius: f64,
+    pub azimuth: f64,
+    pub height: f64
+}
+
+/// The azimuth of a point around the z axis, with PI rather than -PI on the negative x axis
+fn azimuth_of(x: f64, y: f64) -> f64 {
+    let azimuth = y.atan2(x);
+    if azimuth == -PI { PI } else { azimuth }
+}
+
+impl Spherical {
+
+    pub fn new(radius: f64, polar: f64, azimuth: f64) -> Spherical {
+        Spherical { radius, polar, azimuth }
+    }
+
+    /// The spherical coordinates of a point. The angles of the origin are zero
+    pub fn from_cartesian(point: &Vector) -> Spherical {
+        let Vector(x, y, z) = *point;
+        let radius = point.length();
+        let polar = if radius == 0.0 { 0.0 } else { (x * x + y * y).sqrt().atan2(z) };
+        Spherical::new(radius, polar, azimuth_of(x, y))
+    }
+
+    pub fn to_cartesian(&self) -> Vector {
+        let (sin_polar, cos_polar) = self.polar.sin_cos();
+        let (sin_azimuth, cos_azimuth) = self.azimuth.sin_cos();
+        self.radius * Vector(sin_polar * cos_azimuth, sin_polar * sin_azimuth, cos_polar)
+    }
+}
+
+impl Cylindrical {
+
+    pub fn new(radius: f64, azimuth: f64, height: f64) -> Cylindrical {
+        Cylindrical { radius, azimuth, height }
+    }
+
+    /// The cylindrical coordinates of a point. The azimuth of points on the z axis is zero
+    pub fn from_cartesian(point: &Vector) -> Cylindrical {
+        let Vector(x, y, z) = *point;
+        Cylindrical::new((x * x + y * y).sqrt(), azimuth_of(x, y), z)
+    }
+
+    pub fn to_cartesian(&self) -> Vector {
+        let (sin, cos) = self.azimuth.sin_cos();
+        Vector(self.radius * cos, self.radius * sin, self.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn spherical_coordinates() {
+        let point = Vector(1.0, 1.0, 2.0_f64.sqrt());
+        let spherical = Spherical::from_cartesian(&point);
+        assert!((spherical.radius - 2.0).abs() < EPSILON);
+        assert!((spherical.polar - PI / 4.0).abs() < EPSILON);
+        assert!((spherical.azimuth - PI / 4.0).abs() < EPSILON);
+        assert!((spherical.to_cartesian() - point).length() < EPSILON);
+
+        let below = Spherical::from_cartesian(&Vector(-1.0, 0.0, -1.0));
+        assert!((below.polar - 3.0 * PI / 4.0).abs() < EPSILON);
+        assert_eq!(below.azimuth, PI);
+        assert_eq!(Spherical::from_cartesian(&Vector(0.0, 0.0, 0.0)), Spherical::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn cylindrical_coordinates() {
+        let point = Vector(0.0, -3.0, 5.0);
+        let cylindrical = Cylindrical::from_cartesian(&point);
+        assert_eq!(cylindrical, Cylindrical::new(3.0, -PI / 2.0, 5.0));
+        assert!((cylindrical.to_cartesian() - point).length() < EPSILON);
+        assert_eq!(Cylindrical::from_cartesian(&Vector(0.0, 0.0, 2.0)), Cylindrical::new(0.0, 0.0, 2.0));
+    }
+}
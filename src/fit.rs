@@ -0,0 +1,221 @@
+//! Least-squares fitting of plains and lines to point clouds, by principal component analysis,
+//! and of points to plains and lines that almost meet
+
+use crate::{vector::Vector, matrix::Matrix3, line::Line, plain::Plain, math::tolerance};
+
+/// Statistics of the distances of the fitted points from a fitted shape
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Residuals {
+    /// The root mean square of the distances
+    pub rms: f64,
+    /// The largest distance
+    pub max: f64
+}
+
+impl Residuals {
+    /// The statistics of a nonempty list of distances
+    fn of(distances: &[f64]) -> Residuals {
+        let squares = distances.iter().map(|distance| distance * distance).sum::<f64>();
+        Residuals {
+            rms: (squares / distances.len() as f64).sqrt(),
+            max: distances.iter().map(|distance| distance.abs()).fold(0.0, f64::max)
+        }
+    }
+}
+
+/// The plain minimizing the sum of squared orthogonal distances to a set of points
+#[derive(Debug, Clone, Copy)]
+pub struct PlainFit {
+    pub plain: Plain,
+    pub residuals: Residuals
+}
+
+/// The line minimizing the sum of squared orthogonal distances to a set of points
+#[derive(Debug, Clone, Copy)]
+pub struct LineFit {
+    pub line: Line,
+    pub residuals: Residuals
+}
+
+/// The point minimizing the sum of squared distances to plains or lines that should meet, but may miss each other
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeetingPoint {
+    pub point: Vector,
+    /// The distance of the point from each plain or line, in order
+    pub distances: Vec<f64>,
+    pub residuals: Residuals
+}
+
+/// Solve the normal equations `A x = b` of a least-squares problem,
+/// or return None if the symmetric matrix A is rank-deficient, leaving the solution undetermined
+fn solve_normal_equations(a: &Matrix3, b: &Vector) -> Option<Vector> {
+    let (values, _) = a.symmetric_eigen();
+    if tolerance::is_zero(values[2]) || tolerance::is_zero(values[0] / values[2]) {
+        return None;
+    }
+    a.solve(b).ok()
+}
+
+impl MeetingPoint {
+    /// The point nearest to plains in the least-squares sense.
+    /// Returns None if the plumbs of the plains don't span space, e.g. if all the plains share a direction
+    pub fn of_plains(plains: &[Plain]) -> Option<MeetingPoint> {
+        // minimize the sum of (n * x - n * q)^2, for the unit normal n and a point q of each plain
+        let (a, b) = plains.iter().fold((Matrix3::diagonal(0.0, 0.0, 0.0), Vector(0.0, 0.0, 0.0)), |(a, b), plain| {
+            let normal = plain.unit_normal();
+            let point = plain.projection_of(&Vector(0.0, 0.0, 0.0));
+            (a + Matrix3::outer_product(&normal, &normal), b + (normal * point) * normal)
+        });
+        let point = solve_normal_equations(&a, &b)?;
+        let distances: Vec<f64> = plains.iter().map(|plain| plain.distance_from(&point)).collect();
+        Some(MeetingPoint { point, residuals: Residuals::of(&distances), distances })
+    }
+
+    /// The point nearest to lines in the least-squares sense.
+    /// Returns None if the closest point is not unique, e.g. if there is a single line or all the lines are parallel
+    pub fn of_lines(lines: &[Line]) -> Option<MeetingPoint> {
+        // minimize the sum of |P (x - q)|^2, where P projects perpendicularly to the direction of each line through q
+        let (a, b) = lines.iter().fold((Matrix3::diagonal(0.0, 0.0, 0.0), Vector(0.0, 0.0, 0.0)), |(a, b), line| {
+            let direction = (1.0 / line.direction.length()) * line.direction;
+            let projection = Matrix3::identity() - Matrix3::outer_product(&direction, &direction);
+            (a + projection, b + projection * line.point)
+        });
+        let point = solve_normal_equations(&a, &b)?;
+        let distances: Vec<f64> = lines.iter().map(|line| line.distance_from_point(&point)).collect();
+        Some(MeetingPoint { point, residuals: Residuals::of(&distances), distances })
+    }
+}
+
+/// The centroid of points, and the eigen decomposition of their scatter matrix, with the eigenvalues ascending
+fn principal_components(points: &[Vector]) -> Option<(Vector, [f64; 3], [Vector; 3])> {
+    if points.is_empty() {
+        return None;
+    }
+    let centroid = (1.0 / points.len() as f64) * points.iter().fold(Vector(0.0, 0.0, 0.0), |sum, point| sum + *point);
+    let scatter = points.iter().fold(Matrix3::diagonal(0.0, 0.0, 0.0), |sum, point| {
+        let offset = *point - centroid;
+        sum + Matrix3::outer_product(&offset, &offset)
+    });
+    let (values, vectors) = scatter.symmetric_eigen();
+    // the eigenvalues are sums of squares, so rounding is the only source of negative ones
+    Some((centroid, values.map(|value| value.max(0.0)), vectors))
+}
+
+impl PlainFit {
+    /// Fit a plain to points. The plain passes through their centroid,
+    /// perpendicular to the direction in which they spread the least.
+    /// Returns None if there are no points, or if they are all on a single line
+    pub fn of(points: &[Vector]) -> Option<PlainFit> {
+        let (centroid, values, vectors) = principal_components(points)?;
+        // the plain is undetermined when the points spread along a single direction at most
+        if tolerance::is_zero(values[2].sqrt()) || tolerance::is_zero((values[1] / values[2]).sqrt()) {
+            return None;
+        }
+        let plain = Plain::from_plumb(&centroid, &vectors[0]);
+        let distances: Vec<f64> = points.iter().map(|point| plain.distance_from(point)).collect();
+        Some(PlainFit { plain, residuals: Residuals::of(&distances) })
+    }
+}
+
+impl LineFit {
+    /// Fit a line to points. The line passes through their centroid, in the direction in which they spread the most.
+    /// Returns None if there are no points, or if they all coincide
+    pub fn of(points: &[Vector]) -> Option<LineFit> {
+        let (centroid, values, vectors) = principal_components(points)?;
+        if tolerance::is_zero(values[2].sqrt()) {
+            return None;
+        }
+        let line = Line::new(centroid, vectors[2]);
+        let distances: Vec<f64> = points.iter().map(|point| line.distance_from_point(point)).collect();
+        Some(LineFit { line, residuals: Residuals::of(&distances) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn fits_plain() {
+        // a grid on z = 1, alternately displaced by 0.1 up and down
+        let points: Vec<Vector> = (0..25)
+            .map(|i| Vector((i % 5) as f64, (i / 5) as f64, 1.0 + if i % 2 == 0 { 0.1 } else { -0.1 }))
+            .collect();
+        let fit = PlainFit::of(&points).unwrap();
+        assert!((fit.plain.unit_normal().2.abs() - 1.0).abs() < 0.01);
+        assert!(fit.plain.distance_from(&Vector(2.0, 2.0, 1.0)) < 0.01);
+        assert!(fit.residuals.max <= 0.1 + 0.01 && fit.residuals.rms > 0.09);
+
+        let exact = [Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0), Vector(1.0, 1.0, -1.0)];
+        let fit = PlainFit::of(&exact).unwrap();
+        assert!(fit.residuals.max < EPSILON);
+        assert!(exact.iter().all(|point| fit.plain.contains_point(point)));
+    }
+
+    #[test]
+    fn fits_line() {
+        let direction = Vector(1.0, 2.0, -1.0);
+        let offsets = [Vector(0.1, 0.0, 0.1), Vector(-0.1, 0.0, -0.1)];
+        // pairs of points displaced to opposite sides of the line, perpendicularly to it
+        let points: Vec<Vector> = (0..10).map(|i| Vector(1.0, 1.0, 1.0) + ((i / 2) as f64) * direction + offsets[i % 2]).collect();
+        let fit = LineFit::of(&points).unwrap();
+        assert!(Vector::vectoric_product(&fit.line.direction, &direction).length() / direction.length() < EPSILON);
+        assert!(fit.line.is_on_line(&Vector(1.0, 1.0, 1.0)));
+        assert!((fit.residuals.max - 0.02_f64.sqrt()).abs() < EPSILON);
+        assert!((fit.residuals.rms - 0.02_f64.sqrt()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn degenerate_points() {
+        let collinear = [Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 1.0), Vector(2.0, 2.0, 2.0)];
+        assert!(PlainFit::of(&collinear).is_none());
+        assert!(LineFit::of(&collinear).unwrap().residuals.max < EPSILON);
+        assert!(LineFit::of(&[Vector(1.0, 2.0, 3.0); 4]).is_none());
+        assert!(LineFit::of(&[]).is_none());
+    }
+
+    #[test]
+    fn meeting_point_of_plains() {
+        let corner = Vector(1.0, 2.0, 3.0);
+        let normals = [Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0), Vector(1.0, 1.0, 1.0)];
+        let plains: Vec<Plain> = normals.iter().map(|normal| Plain::from_plumb(&corner, normal)).collect();
+        let meeting = MeetingPoint::of_plains(&plains).unwrap();
+        assert!((meeting.point - corner).length() < EPSILON);
+        assert!(meeting.residuals.max < EPSILON);
+
+        // moving the fourth plain away spreads the error over all of them
+        let mut noisy = plains.clone();
+        noisy[3] = Plain::from_plumb(&(corner + Vector(0.1, 0.1, 0.1)), &normals[3]);
+        let meeting = MeetingPoint::of_plains(&noisy).unwrap();
+        assert_eq!(meeting.distances.len(), 4);
+        assert!(meeting.distances.iter().all(|distance| *distance > EPSILON));
+        assert!(meeting.residuals.max < 0.1 * 3.0_f64.sqrt());
+
+        // plains sharing the z direction can't pin the height of the point
+        let vertical: Vec<Plain> = normals[..2].iter().map(|normal| Plain::from_plumb(&corner, normal))
+            .chain([Plain::from_plumb(&corner, &Vector(1.0, -1.0, 0.0))])
+            .collect();
+        assert!(MeetingPoint::of_plains(&vertical).is_none());
+    }
+
+    #[test]
+    fn meeting_point_of_lines() {
+        // sight lines towards a target, from three stations
+        let target = Vector(2.0, -1.0, 4.0);
+        let stations = [Vector(0.0, 0.0, 0.0), Vector(10.0, 0.0, 0.0), Vector(0.0, 10.0, 1.0)];
+        let lines: Vec<Line> = stations.iter().map(|station| Line::from_two_points(*station, &target)).collect();
+        let meeting = MeetingPoint::of_lines(&lines).unwrap();
+        assert!((meeting.point - target).length() < EPSILON);
+
+        // skew lines meet halfway along their common perpendicular
+        let skew = [Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)), Line::new(Vector(0.0, 0.0, 2.0), Vector(0.0, 1.0, 0.0))];
+        let meeting = MeetingPoint::of_lines(&skew).unwrap();
+        assert!((meeting.point - Vector(0.0, 0.0, 1.0)).length() < EPSILON);
+        assert!((meeting.residuals.rms - 1.0).abs() < EPSILON);
+
+        let parallel = [Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)), Line::new(Vector(0.0, 1.0, 0.0), Vector(-2.0, 0.0, 0.0))];
+        assert!(MeetingPoint::of_lines(&parallel).is_none());
+        assert!(MeetingPoint::of_lines(&[]).is_none());
+    }
+}
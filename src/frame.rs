@@ -0,0 +1,138 @@
+//! Coordinate frames: an origin and three axes, relative to the world frame
+
+use crate::{vector::Vector, matrix::Matrix3, affine::Affine3, line::Line, plain::Plain};
+
+/// A frame of local coordinates, where the local point (a, b, c) is the world point
+/// `origin + a * x_axis + b * y_axis + c * z_axis`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    origin: Vector,
+    axes: [Vector; 3],
+    to_world: Affine3,
+    to_local: Affine3
+}
+
+impl Frame {
+
+    /// Generates a frame from its origin and axes, given in world coordinates.
+    /// Fails if the axes are linearly dependent
+    pub fn new(origin: &Vector, x_axis: &Vector, y_axis: &Vector, z_axis: &Vector) -> Result<Frame, ()> {
+        let to_world = Affine3::new(Matrix3::from_columns(x_axis, y_axis, z_axis), *origin);
+        let to_local = to_world.inverse()?;
+        Ok(Frame { origin: *origin, axes: [*x_axis, *y_axis, *z_axis], to_world, to_local })
+    }
+
+    /// The world frame itself
+    pub fn world() -> Frame {
+        let axes = Matrix3::identity().rows();
+        Frame::new(&Vector(0.0, 0.0, 0.0), &axes[0], &axes[1], &axes[2]).unwrap()
+    }
+
+    pub fn origin(&self) -> &Vector {
+        &self.origin
+    }
+
+    /// The x, y and z axes of the frame
+    pub fn axes(&self) -> &[Vector; 3] {
+        &self.axes
+    }
+
+    /// The transformation from local coordinates to world coordinates
+    pub fn to_world(&self) -> &Affine3 {
+        &self.to_world
+    }
+
+    /// The transformation from world coordinates to local coordinates
+    pub fn to_local(&self) -> &Affine3 {
+        &self.to_local
+    }
+
+    /// Express a frame given in the local coordinates of this frame in world coordinates
+    pub fn nested(&self, local: &Frame) -> Frame {
+        let axes = local.axes.map(|axis| self.direction_to_world(&axis));
+        Frame::new(&self.point_to_world(&local.origin), &axes[0], &axes[1], &axes[2]).unwrap()
+    }
+
+    pub fn point_to_world(&self, point: &Vector) -> Vector {
+        self.to_world.apply_to_point(point)
+    }
+
+    pub fn point_to_local(&self, point: &Vector) -> Vector {
+        self.to_local.apply_to_point(point)
+    }
+
+    pub fn direction_to_world(&self, direction: &Vector) -> Vector {
+        self.to_world.apply_to_direction(direction)
+    }
+
+    pub fn direction_to_local(&self, direction: &Vector) -> Vector {
+        self.to_local.apply_to_direction(direction)
+    }
+
+    pub fn line_to_world(&self, line: &Line) -> Line {
+        self.to_world.apply_to_line(line)
+    }
+
+    pub fn line_to_local(&self, line: &Line) -> Line {
+        self.to_local.apply_to_line(line)
+    }
+
+    pub fn plain_to_world(&self, plain: &Plain) -> Plain {
+        // both transformations are invertible, so mapping plains never fails
+        self.to_world.apply_to_plain(plain).unwrap()
+    }
+
+    pub fn plain_to_local(&self, plain: &Plain) -> Plain {
+        self.to_local.apply_to_plain(plain).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::line::relations::LineRelations;
+
+    use super::*;
+    use crate::test_util::assert_close;
+
+    fn sensor() -> Frame {
+        // rotated a quarter turn about z, and moved to (1, 2, 3)
+        Frame::new(&Vector(1.0, 2.0, 3.0), &Vector(0.0, 1.0, 0.0), &Vector(-1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)).unwrap()
+    }
+
+    #[test]
+    fn maps_points_and_directions() {
+        let frame = sensor();
+        assert_close(&frame.point_to_world(&Vector(1.0, 0.0, 0.0)), &Vector(1.0, 3.0, 3.0));
+        assert_close(&frame.point_to_local(&Vector(1.0, 3.0, 3.0)), &Vector(1.0, 0.0, 0.0));
+        assert_close(&frame.direction_to_world(&Vector(1.0, 0.0, 0.0)), &Vector(0.0, 1.0, 0.0));
+        assert_close(&frame.direction_to_local(&Vector(0.0, 1.0, 0.0)), &Vector(1.0, 0.0, 0.0));
+        assert_close(&Frame::world().point_to_local(&Vector(4.0, 5.0, 6.0)), &Vector(4.0, 5.0, 6.0));
+
+        let nested = frame.nested(&sensor());
+        assert_close(&nested.point_to_world(&Vector(0.0, 0.0, 0.0)), &Vector(-1.0, 3.0, 6.0));
+        assert_close(&nested.point_to_world(&Vector(1.0, 0.0, 0.0)), &frame.point_to_world(&sensor().point_to_world(&Vector(1.0, 0.0, 0.0))));
+
+        assert_eq!(Frame::new(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), &Vector(1.0, 1.0, 0.0)), Err(()));
+    }
+
+    #[test]
+    fn preserves_relations_of_lines_and_plains() {
+        // a skewed, non-orthogonal frame
+        let frame = Frame::new(&Vector(1.0, -1.0, 2.0), &Vector(1.0, 0.0, 0.0), &Vector(1.0, 2.0, 0.0), &Vector(0.0, 1.0, 3.0)).unwrap();
+        let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 0.0));
+        let line2 = Line::new(Vector(0.0, 0.0, 0.0), Vector(0.0, 1.0, 1.0));
+        let relation = LineRelations::of(&frame.line_to_world(&line1), &frame.line_to_world(&line2));
+        assert!(matches!(relation, LineRelations::Intersect(_, _)));
+
+        let plain = Plain::through_lines(&[line1, line2]).unwrap();
+        let world_plain = frame.plain_to_world(&plain);
+        assert!(world_plain.contains_line(&frame.line_to_world(&line1)));
+        assert!(world_plain.contains_line(&frame.line_to_world(&line2)));
+        let outside = Vector(0.0, 0.0, 1.0);
+        assert_eq!(world_plain.side_of(&frame.point_to_world(&outside)), plain.side_of(&outside));
+
+        let back = frame.plain_to_local(&world_plain);
+        assert!(back.contains_point(&Vector(1.0, 1.0, 0.0)) && back.contains_point(&Vector(0.0, 1.0, 1.0)));
+        assert!(frame.line_to_local(&frame.line_to_world(&line1)).is_on_line(&Vector(2.0, 2.0, 0.0)));
+    }
+}
@@ -0,0 +1,357 @@
+//! Convex hulls of point sets, computed with the quickhull algorithm
+
+use std::collections::HashMap;
+
+use crate::{vector::Vector, segment::Segment, polygon::Polygon, math::tolerance, plain::Plain};
+
+/// A closed triangulated surface bounding a convex solid
+#[derive(Debug, Clone)]
+pub struct HullSurface {
+    pub vertices: Vec<Vector>,
+    /// The faces as indices into the vertices, ordered counterclockwise when seen from outside
+    pub faces: Vec<[usize; 3]>
+}
+
+/// The convex hull of a set of points, with the dimension the points span
+#[derive(Debug, Clone)]
+pub enum ConvexHull {
+    /// No points were given
+    Empty,
+    /// All points coincide
+    Point(Vector),
+    /// All points are collinear
+    Segment(Segment),
+    /// All points are coplanar
+    Polygon(Polygon),
+    /// The points span a solid
+    Solid(HullSurface)
+}
+
+impl HullSurface {
+    /// The plain of a face, with its plumb pointing out of the hull
+    pub fn face_plain(&self, face: usize) -> Plain {
+        let [a, b, c] = self.faces[face].map(|i| self.vertices[i]);
+        Plain::from_three_points(&a, &b, &c)
+    }
+
+    /// The plains of all faces, with their plumbs pointing out of the hull
+    pub fn face_plains(&self) -> Vec<Plain> {
+        (0..self.faces.len()).map(|face| self.face_plain(face)).collect()
+    }
+
+    /// The outward unit normals of all faces
+    pub fn normals(&self) -> Vec<Vector> {
+        self.face_plains().iter().map(|plain| plain.unit_normal()).collect()
+    }
+
+    pub fn volume(&self) -> f64 {
+        let origin = self.vertices[0];
+        self.faces.iter()
+            .map(|[a, b, c]| Vector::tetrahedron_volume_of_points(&origin, &self.vertices[*a], &self.vertices[*b], &self.vertices[*c]))
+            .sum()
+    }
+
+    /// Check if a point is inside the hull or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        self.face_plains().iter().all(|plain| plain.signed_distance_from(point) < tolerance::EPSILON)
+    }
+}
+
+/// A face of the hull under construction
+struct Face {
+    vertices: [usize; 3],
+    plain: Plain,
+    /// The points in front of the face, not yet known to be inside the hull
+    outside: Vec<usize>,
+    alive: bool
+}
+
+impl ConvexHull {
+    /// Compute the convex hull of a set of points
+    pub fn of(points: &[Vector]) -> ConvexHull {
+        if points.is_empty() {
+            return Self::Empty;
+        }
+        // distances are compared relative to the extent of the coordinates
+        let scale = points.iter().fold(1.0_f64, |scale, Vector(x, y, z)| scale.max(x.abs()).max(y.abs()).max(z.abs()));
+        let epsilon = tolerance::EPSILON * scale;
+
+        // the initial simplex: the farthest pair among the extreme points, then the farthest points from their line and plain
+        let extremes: Vec<usize> = (0..3)
+            .flat_map(|axis| {
+                let coordinate = |i: &usize| [points[*i].0, points[*i].1, points[*i].2][axis];
+                let min = (0..points.len()).min_by(|i, j| coordinate(i).total_cmp(&coordinate(j))).unwrap();
+                let max = (0..points.len()).max_by(|i, j| coordinate(i).total_cmp(&coordinate(j))).unwrap();
+                [min, max]
+            })
+            .collect();
+        let (first, second) = extremes.iter()
+            .flat_map(|i| extremes.iter().map(move |j| (*i, *j)))
+            .max_by(|(i1, j1), (i2, j2)| (points[*i1] - points[*j1]).length().total_cmp(&(points[*i2] - points[*j2]).length()))
+            .unwrap();
+        if (points[second] - points[first]).length() < epsilon {
+            return Self::Point(points[first]);
+        }
+
+        let axis = Segment::new(points[first], points[second]).line();
+        let third = (0..points.len())
+            .max_by(|i, j| axis.distance_from_point(&points[*i]).total_cmp(&axis.distance_from_point(&points[*j])))
+            .unwrap();
+        if axis.distance_from_point(&points[third]) < epsilon {
+            return Self::Segment(collinear_hull(points, &axis.direction));
+        }
+
+        let base = Plain::from_three_points(&points[first], &points[second], &points[third]);
+        let fourth = (0..points.len())
+            .max_by(|i, j| base.distance_from(&points[*i]).total_cmp(&base.distance_from(&points[*j])))
+            .unwrap();
+        if base.distance_from(&points[fourth]) < epsilon {
+            return Self::Polygon(coplanar_hull(points, &base));
+        }
+
+        Self::Solid(quickhull(points, [first, second, third, fourth], epsilon))
+    }
+}
+
+/// The segment between the extreme points along the direction of collinear points
+fn collinear_hull(points: &[Vector], direction: &Vector) -> Segment {
+    let position = |point: &&Vector| **point * (*direction);
+    let start = points.iter().min_by(|p1, p2| position(p1).total_cmp(&position(p2))).unwrap();
+    let end = points.iter().max_by(|p1, p2| position(p1).total_cmp(&position(p2))).unwrap();
+    Segment::new(*start, *end)
+}
+
+/// The convex polygon around coplanar points, counterclockwise around the plumb of their plain,
+/// computed with the monotone chain algorithm in coordinates along two in-plain axes
+fn coplanar_hull(points: &[Vector], plain: &Plain) -> Polygon {
+    let basis = plain.basis();
+    let mut sorted: Vec<(f64, f64, Vector)> = points.iter()
+        .map(|point| { let (u, v) = basis.coordinates_of(point); (u, v, *point) })
+        .collect();
+    sorted.sort_by(|(x1, y1, _), (x2, y2, _)| x1.total_cmp(x2).then(y1.total_cmp(y2)));
+
+    let turns_left = |o: &(f64, f64, Vector), a: &(f64, f64, Vector), b: &(f64, f64, Vector)| {
+        let (u, v) = ((a.0 - o.0, a.1 - o.1), (b.0 - o.0, b.1 - o.1));
+        // compare the sine of the turn rather than the area, so the test doesn't depend on the scale
+        u.0 * v.1 - u.1 * v.0 > tolerance::EPSILON * u.0.hypot(u.1) * v.0.hypot(v.1)
+    };
+    let mut chain: Vec<(f64, f64, Vector)> = vec![];
+    // the lower chain from left to right, then the upper chain back
+    for pass in [sorted.clone(), sorted.into_iter().rev().collect()] {
+        let start = chain.len();
+        for point in pass {
+            while chain.len() >= start + 2 && !turns_left(&chain[chain.len() - 2], &chain[chain.len() - 1], &point) {
+                chain.pop();
+            }
+            chain.push(point);
+        }
+        chain.pop(); // the last point starts the other chain
+    }
+    Polygon::new(chain.into_iter().map(|(_, _, point)| point).collect())
+}
+
+/// Compute the hull of points spanning a solid, starting from a tetrahedron of four of them
+fn quickhull(points: &[Vector], simplex: [usize; 4], epsilon: f64) -> HullSurface {
+    let mut faces: Vec<Face> = vec![];
+    // the face on the left of each directed edge, when seen from outside
+    let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+    let new_face = |faces: &mut Vec<Face>, edges: &mut HashMap<(usize, usize), usize>, vertices: [usize; 3]| {
+        let [a, b, c] = vertices.map(|i| points[i]);
+        let plumb = Vector::vectoric_product(&(b - a), &(c - a));
+        let plain = Plain::from_plumb(&a, &((1.0 / plumb.length()) * plumb));
+        let index = faces.len();
+        for k in 0..3 {
+            edges.insert((vertices[k], vertices[(k + 1) % 3]), index);
+        }
+        faces.push(Face { vertices, plain, outside: vec![], alive: true });
+        index
+    };
+
+    // orient the tetrahedron faces outward, away from the fourth vertex of each
+    let [p0, p1, p2, p3] = simplex;
+    for (face, opposite) in [([p0, p1, p2], p3), ([p0, p3, p1], p2), ([p1, p3, p2], p0), ([p0, p2, p3], p1)] {
+        let [a, b, c] = face.map(|i| points[i]);
+        let outward = Plain::from_three_points(&a, &b, &c).signed_distance_from(&points[opposite]) < 0.0;
+        let oriented = if outward { face } else { [face[0], face[2], face[1]] };
+        new_face(&mut faces, &mut edges, oriented);
+    }
+
+    let assign = |faces: &mut Vec<Face>, candidates: &[usize], point: usize| {
+        if let Some(face) = candidates.iter().find(|face| faces[**face].plain.signed_distance_from(&points[point]) > epsilon) {
+            faces[*face].outside.push(point);
+        }
+    };
+    let initial: Vec<usize> = (0..faces.len()).collect();
+    for point in (0..points.len()).filter(|point| !simplex.contains(point)) {
+        assign(&mut faces, &initial, point);
+    }
+
+    let mut pending: Vec<usize> = initial;
+    while let Some(current) = pending.pop() {
+        if !faces[current].alive || faces[current].outside.is_empty() {
+            continue;
+        }
+        let plain = faces[current].plain;
+        let apex = *faces[current].outside.iter()
+            .max_by(|i, j| plain.signed_distance_from(&points[**i]).total_cmp(&plain.signed_distance_from(&points[**j])))
+            .unwrap();
+
+        // the faces the apex sees, found by walking across edges from the current face
+        let mut visible = vec![current];
+        let mut is_visible: HashMap<usize, bool> = HashMap::from([(current, true)]);
+        let mut horizon: Vec<(usize, usize)> = vec![];
+        let mut k = 0;
+        while k < visible.len() {
+            let face = faces[visible[k]].vertices;
+            for e in 0..3 {
+                let (a, b) = (face[e], face[(e + 1) % 3]);
+                let neighbour = edges[&(b, a)];
+                let sees = *is_visible.entry(neighbour)
+                    .or_insert_with(|| faces[neighbour].plain.signed_distance_from(&points[apex]) > epsilon);
+                if !sees {
+                    horizon.push((a, b));
+                } else if !visible.contains(&neighbour) {
+                    visible.push(neighbour);
+                }
+            }
+            k += 1;
+        }
+
+        let mut orphans: Vec<usize> = vec![];
+        for face in &visible {
+            faces[*face].alive = false;
+            orphans.append(&mut faces[*face].outside);
+            let [a, b, c] = faces[*face].vertices;
+            for edge in [(a, b), (b, c), (c, a)] {
+                if edges.get(&edge) == Some(face) {
+                    edges.remove(&edge);
+                }
+            }
+        }
+        let created: Vec<usize> = horizon.iter().map(|(a, b)| new_face(&mut faces, &mut edges, [*a, *b, apex])).collect();
+        for point in orphans.into_iter().filter(|point| *point != apex) {
+            assign(&mut faces, &created, point);
+        }
+        pending.extend(created);
+    }
+
+    // keep only the vertices of the remaining faces
+    let mut indices: HashMap<usize, usize> = HashMap::new();
+    let mut vertices: Vec<Vector> = vec![];
+    let faces = faces.iter().filter(|face| face.alive)
+        .map(|face| face.vertices.map(|i| *indices.entry(i).or_insert_with(|| {
+            vertices.push(points[i]);
+            vertices.len() - 1
+        })))
+        .collect();
+    HullSurface { vertices, faces }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// Deterministic pseudo-random points in the cube [-1, 1]^3
+    fn random_points(count: usize, seed: u64) -> Vec<Vector> {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            ((state >> 11) as f64) / ((1u64 << 53) as f64) * 2.0 - 1.0
+        };
+        (0..count).map(|_| Vector(next(), next(), next())).collect()
+    }
+
+    fn solid(points: &[Vector]) -> HullSurface {
+        match ConvexHull::of(points) {
+            ConvexHull::Solid(surface) => surface,
+            other => panic!("Expected a solid hull, got {:?}", other)
+        }
+    }
+
+    /// Check that each edge is shared by exactly two faces with opposite directions, and Euler's formula
+    fn assert_closed(surface: &HullSurface) {
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        for [a, b, c] in &surface.faces {
+            for edge in [(*a, *b), (*b, *c), (*c, *a)] {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+        assert!(edges.iter().all(|((a, b), count)| *count == 1 && edges.get(&(*b, *a)) == Some(&1)));
+        assert_eq!(surface.vertices.len() as i64 - (edges.len() / 2) as i64 + surface.faces.len() as i64, 2);
+    }
+
+    #[test]
+    fn cube_with_inner_points() {
+        let mut points = random_points(200, 7);
+        for x in [0.0, 3.0] {
+            for y in [0.0, 3.0] {
+                for z in [0.0, 3.0] {
+                    points.push(Vector(x, y, z) - Vector(1.5, 1.5, 1.5));
+                }
+            }
+        }
+        let surface = solid(&points);
+        assert_eq!(surface.vertices.len(), 8);
+        assert_eq!(surface.faces.len(), 12);
+        assert!((surface.volume() - 27.0).abs() < EPSILON);
+        assert_closed(&surface);
+        assert!(points.iter().all(|point| surface.contains_point(point)));
+    }
+
+    #[test]
+    fn outward_faces() {
+        let surface = solid(&random_points(500, 3));
+        assert_closed(&surface);
+        let center = Vector(0.0, 0.0, 0.0);
+        assert!(surface.face_plains().iter().all(|plain| plain.signed_distance_from(&center) < 0.0));
+        assert!(surface.normals().iter().all(|normal| (normal.length() - 1.0).abs() < EPSILON));
+    }
+
+    #[test]
+    fn many_points() {
+        let points = random_points(100_000, 11);
+        let surface = solid(&points);
+        assert_closed(&surface);
+        assert!(points.iter().step_by(97).all(|point| surface.contains_point(point)));
+        assert!(surface.volume() > 7.9 && surface.volume() <= 8.0);
+    }
+
+    #[test]
+    fn degenerate_inputs() {
+        assert!(matches!(ConvexHull::of(&[]), ConvexHull::Empty));
+        let point = Vector(1.0, 2.0, 3.0);
+        assert!(matches!(ConvexHull::of(&[point, point]), ConvexHull::Point(p) if p == point));
+
+        let collinear = [Vector(1.0, 1.0, 1.0), Vector(3.0, 3.0, 3.0), Vector(-1.0, -1.0, -1.0), Vector(2.0, 2.0, 2.0)];
+        match ConvexHull::of(&collinear) {
+            ConvexHull::Segment(segment) => {
+                let ends = [segment.start, segment.end];
+                assert!(ends.contains(&Vector(-1.0, -1.0, -1.0)) && ends.contains(&Vector(3.0, 3.0, 3.0)));
+            },
+            other => panic!("Expected a segment, got {:?}", other)
+        }
+
+        let coplanar: Vec<Vector> = random_points(100, 5).iter().map(|Vector(x, y, _)| Vector(*x, *y, 2.0))
+            .chain([Vector(-2.0, -2.0, 2.0), Vector(2.0, -2.0, 2.0), Vector(2.0, 2.0, 2.0), Vector(-2.0, 2.0, 2.0), Vector(0.0, 2.0, 2.0)])
+            .collect();
+        match ConvexHull::of(&coplanar) {
+            ConvexHull::Polygon(polygon) => {
+                assert_eq!(polygon.vertices().len(), 4);
+                assert_eq!(polygon.area(), 16.0);
+                assert!(polygon.is_convex());
+            },
+            other => panic!("Expected a polygon, got {:?}", other)
+        }
+
+        // a small square, with turns much smaller than the tolerance in absolute terms
+        let small = [Vector(0.0, 0.0, 0.0), Vector(1e-5, 0.0, 0.0), Vector(0.0, 1e-5, 0.0), Vector(1e-5, 1e-5, 0.0), Vector(5e-6, 5e-6, 0.0)];
+        match ConvexHull::of(&small) {
+            ConvexHull::Polygon(polygon) => {
+                assert_eq!(polygon.vertices().len(), 4);
+                assert!((polygon.area() - 1e-10).abs() < 1e-15);
+            },
+            other => panic!("Expected a polygon, got {:?}", other)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Analytic geometry of points, lines and planes in space
+
+// Fallible computations report failure as `Result<_, ()>` throughout the crate
+#![allow(clippy::result_unit_err)]
+
+pub mod vector;
+pub mod line;
+pub mod plain;
+pub mod segment;
+pub mod ray;
+pub mod triangle;
+pub mod sphere;
+pub mod circle;
+pub mod tetrahedron;
+pub mod pyramid;
+pub mod aabb;
+pub mod polyhedron;
+pub mod polygon;
+pub mod hull;
+pub mod bsp;
+pub mod matrix;
+pub mod affine;
+pub mod quaternion;
+pub mod coordinates;
+pub mod frame;
+pub mod plane2d;
+pub mod fit;
+pub mod projective;
+pub mod math;
+
+#[cfg(test)]
+mod test_util;
@@ -1,14 +1,15 @@
 //! A line module
 
 use std::f64::consts::PI;
-use crate::vector::Vector;
-use crate::math::equation::EquationSolution;
+use crate::{vector::Vector, plain::Plain, affine::Affine3};
+use crate::math::{equation::EquationSolution, tolerance};
 
 use self::relations::LineRelations;
 
 pub mod relations;
+pub(crate) mod closest;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Line {
     pub point: Vector,
     pub direction: Vector    
@@ -26,10 +27,43 @@ impl Line {
     }
 
     pub fn is_on_line(&self, other_point: &Vector) -> bool {
-        self.distance_from_point(other_point) == 0.0
+        tolerance::is_zero(self.distance_from_point(other_point))
     }
 
-    fn distance_from_point(&self, other_point: &Vector) -> f64 {
+    /// Find the point of the line closest to a given point
+    pub fn projection_of(&self, other_point: &Vector) -> Vector {
+        let t = closest::closest_parameter_to(self, closest::UNBOUNDED, other_point);
+        self.point + t * self.direction
+    }
+
+    /// Find the common line of a set of points.
+    /// Returns None if the points are not collinear, or if they all coincide and don't determine a single line
+    pub fn through_points(points: &[Vector]) -> Option<Line> {
+        let origin = *points.first()?;
+        // use the farthest point from the origin for the direction, to reduce rounding errors
+        let farthest = points.iter()
+            .max_by(|p1, p2| (**p1 - origin).length().total_cmp(&(**p2 - origin).length()))?;
+        if tolerance::is_zero((*farthest - origin).length()) {
+            return None;
+        }
+
+        let line = Line::from_two_points(origin, farthest);
+        if points.iter().all(|point| line.is_on_line(point)) { Some(line) } else { None }
+    }
+
+    /// Check if all of the given points lie on a single line
+    pub fn are_collinear(points: &[Vector]) -> bool {
+        match points.first() {
+            None => true,
+            Some(origin) => {
+                let all_coincide = points.iter().all(|point| tolerance::is_zero((*point - *origin).length()));
+                all_coincide || Line::through_points(points).is_some()
+            }
+        }
+    }
+
+    /// Calculate the distance between a given point and this line
+    pub fn distance_from_point(&self, other_point: &Vector) -> f64 {
         // Find a point p1 = p + tu where op1 * direction = 0
         // for every dimension of p1:
         // xp1 = xp + tu1
@@ -59,8 +93,8 @@ impl Line {
         // find a point such that p1 + tu1 = p2 + su2
         // for dimension x: xp1 + t * xu1 = xp2 + s * xu2
         // simplify: t * xu1 - s * xu2 = xp2 - xp1;
-        // therefore, for the entire vector: tu1 - su2 = p2 - p1;
-        let Vector(constx, consty, constz) = line2.point - line1.point;
+        // therefore, for the entire vector: tu1 - su2 + (p1 - p2) = 0;
+        let Vector(constx, consty, constz) = line1.point - line2.point;
         let Vector(coefficient_tx, coefficient_ty, coefficient_tz) = line1.direction;
         let Vector(coefficient_sx, coefficient_sy, coefficient_sz) = line2.direction;
 
@@ -92,6 +126,65 @@ impl Line {
         }
     }
 
+    /// The component of the vector from the line to a point that is perpendicular to the line
+    /// # Panics:
+    /// - If the point is on the line
+    fn perpendicular_offset(&self, other_point: &Vector) -> Vector {
+        let offset = *other_point - self.projection_of(other_point);
+        if tolerance::is_zero(offset.length()) {
+            panic!("A point on the edge does not determine a half-plane")
+        }
+        offset
+    }
+
+    /// Compute the dihedral angle (0 <= x <= PI) between two half-planes sharing this line as their edge,
+    /// where each half-plane is given by a point on it
+    /// # Panics:
+    /// - If one of the points is on the line
+    pub fn dihedral_angle(&self, point1: &Vector, point2: &Vector) -> f64 {
+        let (offset1, offset2) = (self.perpendicular_offset(point1), self.perpendicular_offset(point2));
+        Vector::vectoric_product(&offset1, &offset2).length().atan2(offset1 * offset2)
+    }
+
+    /// Compute the signed dihedral angle (-PI < x <= PI) rotating the half-plane through the first point
+    /// to the half-plane through the second one, counterclockwise around the direction of this line
+    /// by the right hand rule
+    /// # Panics:
+    /// - If one of the points is on the line
+    pub fn signed_dihedral_angle(&self, point1: &Vector, point2: &Vector) -> f64 {
+        let (offset1, offset2) = (self.perpendicular_offset(point1), self.perpendicular_offset(point2));
+        let sine = Vector::vectoric_product(&offset1, &offset2) * self.direction / self.direction.length();
+        sine.atan2(offset1 * offset2)
+    }
+
+    /// Rotate a point by an angle about this line, counterclockwise around its direction by the right hand rule
+    pub fn rotate_point(&self, point: &Vector, angle: f64) -> Vector {
+        Affine3::rotation_about(self, angle).apply_to_point(point)
+    }
+
+    /// Rotate a line by an angle about this line
+    pub fn rotate_line(&self, line: &Line, angle: f64) -> Line {
+        Affine3::rotation_about(self, angle).apply_to_line(line)
+    }
+
+    /// Rotate a plain by an angle about this line
+    pub fn rotate_plain(&self, plain: &Plain, angle: f64) -> Plain {
+        // a rotation is never singular, so it cannot flatten the plain
+        Affine3::rotation_about(self, angle).apply_to_plain(plain).expect("A rotation is invertible")
+    }
+
+    /// Recover the angle (-PI < x <= PI) rotating a point about this line to its image.
+    /// Fails if the point is on the line, or if no rotation about it maps the point to the image
+    pub fn rotation_angle(&self, point: &Vector, image: &Vector) -> Result<f64, ()> {
+        let (projection, image_projection) = (self.projection_of(point), self.projection_of(image));
+        let radius = self.distance_from_point(point);
+        if tolerance::is_zero(radius) || !tolerance::is_zero((projection - image_projection).length() / radius)
+            || !tolerance::approx_eq(self.distance_from_point(image) / radius, 1.0) {
+            return Err(());
+        }
+        Ok(self.signed_dihedral_angle(point, image))
+    }
+
     /// Find the angle (0 < x < PI/2) between two lines, in radians
     pub fn angle_between(line1: &Line, line2: &Line) -> f64 {
         let angle = Vector::angle_between(&line1.direction, &line2.direction);
@@ -113,6 +206,7 @@ impl PartialEq for Line {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::EPSILON;
 
     #[test]
     fn distance_works() {
@@ -125,11 +219,56 @@ mod tests {
         assert_eq!(distance, 0.0);
     }
 
+    #[test]
+    fn projection_works() {
+        let line = Line::new(Vector(1.0, 0.0, 0.0), Vector(0.0, 2.0, 0.0));
+        assert_eq!(line.projection_of(&Vector(4.0, 3.0, 0.0)), Vector(1.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn collinear_points() {
+        let points = [Vector(1.0, 1.0, 1.0), Vector(3.0, 3.0, 3.0), Vector(-2.0, -2.0, -2.0)];
+        let line = Line::through_points(&points).unwrap();
+        assert!(line.is_on_line(&Vector(0.0, 0.0, 0.0)));
+        assert!(Line::are_collinear(&points));
+        assert!(Line::are_collinear(&[Vector(1.0, 1.0, 1.0), Vector(1.0, 1.0, 1.0)]));
+        assert!(Line::through_points(&[Vector(1.0, 1.0, 1.0), Vector(1.0, 1.0, 1.0)]).is_none());
+    }
+
+    #[test]
+    fn non_collinear_points() {
+        let points = [Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0)];
+        assert!(Line::through_points(&points).is_none());
+        assert!(!Line::are_collinear(&points));
+    }
+
     #[test]
     fn intersection_works() {
         let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)); // the x axis
         let line2 = Line::new(Vector(0.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0)); // the y axis
-        assert_eq!(Line::intersection(&line1, &line2).unwrap(), Vector(0.0, 0.0, 0.0))
+        assert_eq!(Line::intersection(&line1, &line2).unwrap(), Vector(0.0, 0.0, 0.0));
+        // far from the origin, the offsets are much larger than the directions
+        let far = Line::new(Vector(1e5, 1e5, 0.0), Vector(0.0, 1.0, 0.0));
+        assert_eq!(Line::intersection(&line1, &far).unwrap(), Vector(1e5, 0.0, 0.0));
+    }
+
+    #[test]
+    fn dihedral_angles() {
+        let edge = Line::new(Vector(0.0, 0.0, 1.0), Vector(0.0, 0.0, 2.0)); // the z axis
+        let (on_x, on_y) = (Vector(3.0, 0.0, 5.0), Vector(0.0, 1.0, -2.0));
+        let behind = Vector(-1.0, -1.0, 0.0);
+        assert!((edge.dihedral_angle(&on_x, &on_y) - PI / 2.0).abs() < EPSILON);
+        assert!((edge.dihedral_angle(&on_x, &behind) - 3.0 * PI / 4.0).abs() < EPSILON);
+        assert!((edge.signed_dihedral_angle(&on_x, &on_y) - PI / 2.0).abs() < EPSILON);
+        assert!((edge.signed_dihedral_angle(&on_y, &on_x) + PI / 2.0).abs() < EPSILON);
+        assert!((edge.signed_dihedral_angle(&on_x, &behind) + 3.0 * PI / 4.0).abs() < EPSILON);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dihedral_angle_with_point_on_edge() {
+        let edge = Line::new(Vector(0.0, 0.0, 0.0), Vector(0.0, 0.0, 1.0));
+        edge.dihedral_angle(&Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 4.0));
     }
 
     #[test]
@@ -138,4 +277,28 @@ mod tests {
         let line2 = Line::new(Vector(0.0, 0.0, 0.0), Vector(-1.0, 1.0, 0.0));
         assert_eq!(Line::angle_between(&line1, &line2), PI / 4.0)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn rotates_about_axis() {
+        let axis = Line::new(Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0));
+        let point = Vector(2.0, 5.0, 0.0);
+        let image = axis.rotate_point(&point, PI / 2.0);
+        assert!((image - Vector(1.0, 5.0, -1.0)).length() < EPSILON);
+        assert!((axis.rotation_angle(&point, &image).unwrap() - PI / 2.0).abs() < EPSILON);
+        let back = axis.rotate_point(&point, -2.0);
+        assert!((axis.rotation_angle(&point, &back).unwrap() + 2.0).abs() < EPSILON);
+
+        assert_eq!(axis.rotation_angle(&Vector(1.0, 3.0, 0.0), &Vector(1.0, 3.0, 0.0)), Err(()));
+        assert_eq!(axis.rotation_angle(&point, &Vector(1.0, 6.0, -1.0)), Err(()));
+        assert_eq!(axis.rotation_angle(&point, &Vector(1.0, 5.0, -2.0)), Err(()));
+
+        let line = Line::new(Vector(2.0, 0.0, 0.0), Vector(0.0, 0.0, 1.0));
+        let rotated = axis.rotate_line(&line, PI);
+        assert!(rotated.is_on_line(&Vector(0.0, 0.0, 0.0)) && rotated.is_on_line(&Vector(0.0, 0.0, 3.0)));
+
+        let plain = Plain::from_plumb(&Vector(0.0, 0.0, 1.0), &Vector(0.0, 0.0, 1.0));
+        let rotated = axis.rotate_plain(&plain, PI / 2.0);
+        assert!(rotated.contains_point(&Vector(2.0, 0.0, 0.0)));
+        assert!((rotated.unit_normal() - Vector(1.0, 0.0, 0.0)).length() < EPSILON);
+    }
+}
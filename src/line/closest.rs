@@ -0,0 +1,95 @@
+//! Closest points between bounded parts of lines
+
+use crate::{vector::Vector, math::tolerance};
+
+use super::Line;
+
+/// The range of parameters of an infinite line
+pub const UNBOUNDED: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+
+/// Find the parameters of the closest points of two lines, where each parameter is restricted to the given bounds.
+/// Parameters are of the form `point + t * direction`.
+pub fn closest_parameters(line1: &Line, bounds1: (f64, f64), line2: &Line, bounds2: (f64, f64)) -> (f64, f64) {
+    // minimize |p1 + s*u1 - p2 - t*u2|^2 over s and t, clamping each one to its bounds
+    let clamp1 = |s: f64| s.clamp(bounds1.0, bounds1.1);
+    let clamp2 = |t: f64| t.clamp(bounds2.0, bounds2.1);
+    let (u1, u2) = (line1.direction, line2.direction);
+    let difference = line1.point - line2.point;
+    let (a, b, e) = (u1 * u1, u1 * u2, u2 * u2);
+    let (c, f) = (u1 * difference, u2 * difference);
+
+    if tolerance::is_zero(a) && tolerance::is_zero(e) {
+        return (clamp1(0.0), clamp2(0.0));
+    }
+    if tolerance::is_zero(a) {
+        let s = clamp1(0.0);
+        return (s, clamp2((b * s + f) / e));
+    }
+    if tolerance::is_zero(e) {
+        let t = clamp2(0.0);
+        return (clamp1((b * t - c) / a), t);
+    }
+
+    let denominator = a * e - b * b;
+    // parallel lines have no single closest pair, start from any parameter of the first line
+    let s = if tolerance::is_zero(denominator / (a * e)) {
+        clamp1(0.0)
+    } else {
+        clamp1((b * f - c * e) / denominator)
+    };
+    // the best t for s, and if it is clamped, the best s for the clamped t
+    let t = (b * s + f) / e;
+    if t < bounds2.0 {
+        (clamp1((b * bounds2.0 - c) / a), bounds2.0)
+    } else if t > bounds2.1 {
+        (clamp1((b * bounds2.1 - c) / a), bounds2.1)
+    } else {
+        (s, t)
+    }
+}
+
+/// Find the parameter of the closest point to a given point, restricted to the given bounds
+pub fn closest_parameter_to(line: &Line, bounds: (f64, f64), point: &Vector) -> f64 {
+    let length = line.direction * line.direction;
+    if tolerance::is_zero(length) {
+        return 0.0_f64.clamp(bounds.0, bounds.1);
+    }
+    ((*point - line.point) * line.direction / length).clamp(bounds.0, bounds.1)
+}
+
+/// Find the parameter of a point known to be on the line
+pub fn parameter_of(line: &Line, point: &Vector) -> f64 {
+    (*point - line.point) * line.direction / (line.direction * line.direction)
+}
+
+/// Check if a parameter is within bounds, up to the tolerance
+pub fn within(bounds: (f64, f64), t: f64) -> bool {
+    t > bounds.0 - tolerance::EPSILON && t < bounds.1 + tolerance::EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn closest_between_foreign_lines() {
+        let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0));
+        let line2 = Line::new(Vector(3.0, -1.0, 1.0), Vector(0.0, 1.0, 0.0));
+        assert_eq!(closest_parameters(&line1, UNBOUNDED, &line2, UNBOUNDED), (3.0, 1.0));
+        assert_eq!(closest_parameters(&line1, (0.0, 1.0), &line2, (0.0, 0.5)), (1.0, 0.5));
+    }
+
+    #[test]
+    fn closest_between_parallel_lines() {
+        let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0));
+        let line2 = Line::new(Vector(5.0, 1.0, 0.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(closest_parameters(&line1, (0.0, 1.0), &line2, (0.0, 1.0)), (1.0, 0.0));
+    }
+
+    #[test]
+    fn closest_to_point() {
+        let line = Line::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0));
+        assert_eq!(closest_parameter_to(&line, UNBOUNDED, &Vector(1.0, 4.0, 0.0)), 0.5);
+        assert_eq!(closest_parameter_to(&line, (0.0, f64::INFINITY), &Vector(-1.0, 4.0, 0.0)), 0.0);
+    }
+}
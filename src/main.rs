@@ -1,8 +1,3 @@
-mod vector;
-mod line;
-mod plain;
-mod math;
-
 fn main() {
     println!("Hello, world!");
 }
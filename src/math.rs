@@ -1,3 +1,5 @@
 pub mod ratio;
 pub mod dependence;
-pub mod equation;
\ No newline at end of file
+pub mod equation;
+pub mod tolerance;
+pub mod linear_program;
\ No newline at end of file
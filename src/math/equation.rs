@@ -1,6 +1,6 @@
 //! A module for solving linear equations
 
-use crate::vector::Vector;
+use super::tolerance;
 
 pub enum EquationSolution {
     /// No solution (0x = -b, b != 0)
@@ -17,57 +17,24 @@ impl EquationSolution {
         if a == 0.0 {
             if b == 0.0 { Self::Undefined } else { Self::None }
         } else {
-            Self::Real(-1.0 * b / a)
-        }
-    }
-
-    /// compute the x solution to an equation system, given the y solution
-    fn compute_other_solution(eq: (f64, f64, f64), y: f64) -> Option<(f64, f64)> {
-        // we have a solution for y, compute solution for x
-        if let EquationSolution::Real(x) = EquationSolution::compute(eq.0, eq.1 * y + eq.2) {
-            Some((x, y))
-        } else {
-            None
+            Self::Real(-b / a)
         }
     }
 
     /// solve the equation system: `ax + by + c = 0`, `mx + ny + k = 0`
     /// return an optional tuple of solutions, if found.
     pub fn compute_multiple(eq1: (f64, f64, f64), eq2: (f64, f64, f64)) -> Option<(f64, f64)> {
-        // given ax + by + c = 0,
-        //       mx + ny + k = 0  - Multiply this by a/m
-        // we now have:
-        // ax + by + c = 0
-        // ax + (a*n/m)y + (a*k/m) = 0
-        // this equation is solveable for b
-        // special case: a = 0 or m = 0, solve for y immediately
-        let y_coefficient: f64;
-        let constant: f64;
-        let mut eq_compute_later = eq1;
-        if eq1.0 == 0.0 {
-            y_coefficient = eq1.1;
-            eq_compute_later = eq2;
-            constant = eq1.2;
-        } else if eq2.0 == 0.0 {
-            y_coefficient = eq2.1;
-            constant = eq2.2;
-        } else {
-            // subtract two equations
-            let multiplier = eq1.0 / eq2.0;
-            let eq1 = Vector(eq1.0, eq1.1, eq1.2);
-            let eq2 = multiplier * Vector(eq2.0, eq2.1, eq2.2);
-            let difference = eq1 - eq2;
-            y_coefficient = difference.1;
-            constant = difference.2;
+        let (a, b, c) = eq1;
+        let (m, n, k) = eq2;
+        // the equations are parallel when the sine of the angle between their coefficient vectors vanishes.
+        // the constants don't take part, so large offsets don't make a solvable system look singular
+        let determinant = a * n - b * m;
+        let scale = a.hypot(b) * m.hypot(n);
+        if scale == 0.0 || tolerance::is_zero(determinant / scale) {
+            return None;
         }
-        
-        if let EquationSolution::Real(y) = EquationSolution::compute(y_coefficient, constant) {
-            // we have a solution for y, compute solution for x
-            EquationSolution::compute_other_solution(eq_compute_later, y)
-        } else {
-            None
-        }
-        
+        // Cramer's rule
+        Some(((b * k - c * n) / determinant, (c * m - a * k) / determinant))
     }
 }
 
@@ -137,6 +104,15 @@ mod tests {
         assert_eq!(EquationSolution::compute_multiple(eq1, eq2).unwrap(), (1.0, 1.0))
     }
 
+    #[test]
+    fn large_constants() {
+        let eq1 = (1.0, 0.0, -1e5);
+        let eq2 = (0.0, 1.0, -1e5);
+        assert_eq!(EquationSolution::compute_multiple(eq1, eq2).unwrap(), (1e5, 1e5));
+        let eq3 = (2.0, 0.0, -3e5);
+        assert!(EquationSolution::compute_multiple(eq1, eq3).is_none());
+    }
+
     #[test]
     fn y_solution_immediate() {
         let eq1 = (0.0, 1.0, -2.0); // y=2
@@ -0,0 +1,266 @@
+//! A module for solving linear programs over half-spaces in space
+
+use crate::{vector::Vector, plain::{half_space::HalfSpace, side::Side}};
+
+use super::tolerance;
+
+#[derive(Debug, PartialEq)]
+pub enum LinearProgramSolution {
+    /// The half-spaces have no common point
+    Infeasible,
+    /// The objective grows without limit in the common region of the half-spaces
+    Unbounded,
+    /// An optimal point, and the indices of the half-spaces whose boundaries pass through it.
+    /// When the optimum is attained along an edge or a face, this is any of its points,
+    /// not necessarily a vertex: a slab, for example, has no vertices at all
+    Optimal(Vector, Vec<usize>)
+}
+
+impl LinearProgramSolution {
+    /// Maximize `objective * x` over the points `x` common to all of the half-spaces
+    pub fn maximize(objective: &Vector, half_spaces: &[HalfSpace]) -> Self {
+        let mut tableau = Tableau::new(half_spaces);
+
+        // phase 1: find a feasible basis, by driving the artificial variables to zero
+        let phase1_costs: Vec<f64> = (0..tableau.columns()).map(|j| if tableau.is_artificial(j) { -1.0 } else { 0.0 }).collect();
+        tableau.optimize(&phase1_costs, true);
+        if !tolerance::is_zero(tableau.value(&phase1_costs)) {
+            return Self::Infeasible;
+        }
+        tableau.drive_out_artificials();
+
+        // phase 2: optimize the objective, with each coordinate split into a positive and a negative part
+        let Vector(c1, c2, c3) = *objective;
+        let mut phase2_costs = vec![0.0; tableau.columns()];
+        phase2_costs[..6].copy_from_slice(&[c1, c2, c3, -c1, -c2, -c3]);
+        if !tableau.optimize(&phase2_costs, false) {
+            return Self::Unbounded;
+        }
+
+        let solution = tableau.solution();
+        let point = Vector(solution[0] - solution[3], solution[1] - solution[4], solution[2] - solution[5]);
+        let active = half_spaces.iter().enumerate()
+            .filter(|(_, half_space)| half_space.boundary().side_of(&point) == Side::On)
+            .map(|(i, _)| i)
+            .collect();
+        Self::Optimal(point, active)
+    }
+
+    /// Minimize `objective * x` over the points `x` common to all of the half-spaces
+    pub fn minimize(objective: &Vector, half_spaces: &[HalfSpace]) -> Self {
+        Self::maximize(&(-*objective), half_spaces)
+    }
+}
+
+/// A simplex tableau for the constraints `plumb * x + slack = -d`, with artificial variables for negative right hand sides.
+/// The columns are the positive parts of x, the negative parts of x, the slacks and the artificials, followed by the right hand side
+struct Tableau {
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+    first_artificial: usize
+}
+
+impl Tableau {
+    fn new(half_spaces: &[HalfSpace]) -> Self {
+        let count = half_spaces.len();
+        let bounds: Vec<(Vector, f64)> = half_spaces.iter()
+            .map(|half_space| {
+                // the half-space is plumb * x + d <= 0
+                let plumb = half_space.boundary().unit_normal();
+                (plumb, half_space.boundary().signed_distance_from(&Vector(0.0, 0.0, 0.0)))
+            })
+            .collect();
+        let artificial_rows: Vec<usize> = (0..count).filter(|i| bounds[*i].1 > 0.0).collect();
+        let first_artificial = 6 + count;
+        let columns = first_artificial + artificial_rows.len();
+
+        let mut rows = vec![vec![0.0; columns + 1]; count];
+        let mut basis = vec![0; count];
+        for (i, (plumb, distance)) in bounds.iter().enumerate() {
+            let Vector(a, b, c) = *plumb;
+            let row = &mut rows[i];
+            row[..6].copy_from_slice(&[a, b, c, -a, -b, -c]);
+            row[6 + i] = 1.0;
+            row[columns] = -distance;
+            basis[i] = 6 + i;
+        }
+        for (k, i) in artificial_rows.iter().enumerate() {
+            // negate the row so its right hand side is positive, and start from the artificial variable
+            let row = &mut rows[*i];
+            row.iter_mut().for_each(|value| *value = -*value);
+            row[first_artificial + k] = 1.0;
+            basis[*i] = first_artificial + k;
+        }
+        Tableau { rows, basis, first_artificial }
+    }
+
+    fn columns(&self) -> usize {
+        self.rows.first().map_or(self.first_artificial, |row| row.len() - 1)
+    }
+
+    fn is_artificial(&self, column: usize) -> bool {
+        column >= self.first_artificial
+    }
+
+    /// The value of the variables of the current basic solution
+    fn solution(&self) -> Vec<f64> {
+        let mut solution = vec![0.0; self.columns()];
+        for (row, column) in self.rows.iter().zip(self.basis.iter()) {
+            solution[*column] = row[row.len() - 1];
+        }
+        solution
+    }
+
+    /// The value of a given objective at the current basic solution
+    fn value(&self, costs: &[f64]) -> f64 {
+        self.solution().iter().zip(costs.iter()).map(|(value, cost)| value * cost).sum()
+    }
+
+    fn pivot(&mut self, pivot_row: usize, pivot_column: usize) {
+        let pivot = self.rows[pivot_row][pivot_column];
+        self.rows[pivot_row].iter_mut().for_each(|value| *value /= pivot);
+        let normalized = self.rows[pivot_row].clone();
+        for (i, row) in self.rows.iter_mut().enumerate() {
+            let factor = row[pivot_column];
+            if i != pivot_row && factor != 0.0 {
+                row.iter_mut().zip(normalized.iter()).for_each(|(value, pivot_value)| *value -= factor * pivot_value);
+            }
+        }
+        self.basis[pivot_row] = pivot_column;
+    }
+
+    /// Maximize the given objective with Bland's rule, which never cycles.
+    /// Returns false if the objective is unbounded
+    fn optimize(&mut self, costs: &[f64], allow_artificials: bool) -> bool {
+        loop {
+            let reduced_cost = |column: usize| costs[column] - self.rows.iter().zip(self.basis.iter())
+                .map(|(row, basic)| costs[*basic] * row[column])
+                .sum::<f64>();
+            let entering = (0..self.columns())
+                .filter(|column| allow_artificials || !self.is_artificial(*column))
+                .find(|column| reduced_cost(*column) > tolerance::EPSILON);
+            let entering = match entering {
+                None => return true,
+                Some(column) => column
+            };
+
+            let rhs = self.columns();
+            let leaving = (0..self.rows.len())
+                .filter(|i| self.rows[*i][entering] > tolerance::EPSILON)
+                .min_by(|i, j| {
+                    let ratio_i = self.rows[*i][rhs] / self.rows[*i][entering];
+                    let ratio_j = self.rows[*j][rhs] / self.rows[*j][entering];
+                    ratio_i.total_cmp(&ratio_j).then(self.basis[*i].cmp(&self.basis[*j]))
+                });
+            match leaving {
+                None => return false,
+                Some(row) => self.pivot(row, entering)
+            }
+        }
+    }
+
+    /// Replace artificial variables left in the basis at zero with original ones, where possible
+    fn drive_out_artificials(&mut self) {
+        for i in 0..self.rows.len() {
+            if !self.is_artificial(self.basis[i]) {
+                continue;
+            }
+            let replacement = (0..self.first_artificial).find(|column| !tolerance::is_zero(self.rows[i][*column]));
+            if let Some(column) = replacement {
+                self.pivot(i, column);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plain::Plain;
+
+    use super::*;
+
+    /// The half-spaces bounding the unit cube [0, 1]^3
+    fn unit_cube() -> Vec<HalfSpace> {
+        let (origin, corner) = (Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 1.0));
+        let (x, y, z) = (Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        vec![
+            HalfSpace::in_front_of(Plain::from_plumb(&origin, &x)),
+            HalfSpace::in_front_of(Plain::from_plumb(&origin, &y)),
+            HalfSpace::in_front_of(Plain::from_plumb(&origin, &z)),
+            HalfSpace::behind(Plain::from_plumb(&corner, &x)),
+            HalfSpace::behind(Plain::from_plumb(&corner, &y)),
+            HalfSpace::behind(Plain::from_plumb(&corner, &z)),
+        ]
+    }
+
+    #[test]
+    fn optimal_vertex() {
+        let solution = LinearProgramSolution::maximize(&Vector(1.0, 2.0, 3.0), &unit_cube());
+        assert_eq!(solution, LinearProgramSolution::Optimal(Vector(1.0, 1.0, 1.0), vec![3, 4, 5]));
+        let solution = LinearProgramSolution::minimize(&Vector(1.0, -2.0, 3.0), &unit_cube());
+        assert_eq!(solution, LinearProgramSolution::Optimal(Vector(0.0, 1.0, 0.0), vec![0, 2, 4]));
+    }
+
+    #[test]
+    fn optimal_vertex_away_from_origin() {
+        // the cube [2, 3]^3 does not contain the origin, and needs the first phase to find a vertex
+        let cube: Vec<HalfSpace> = unit_cube().iter()
+            .map(|half_space| {
+                let boundary = half_space.boundary();
+                let shifted = Plain::from_plumb(&(boundary.projection_of(&Vector(0.0, 0.0, 0.0)) + Vector(2.0, 2.0, 2.0)), &boundary.unit_normal());
+                HalfSpace::behind(shifted)
+            })
+            .collect();
+        let solution = LinearProgramSolution::maximize(&Vector(-1.0, 1.0, -1.0), &cube);
+        assert_eq!(solution, LinearProgramSolution::Optimal(Vector(2.0, 3.0, 2.0), vec![0, 2, 4]));
+    }
+
+    #[test]
+    fn cut_cube() {
+        // x + y + z <= 1.5 cuts the corner, and the optimum of x + y + 2z is on the cut
+        let mut half_spaces = unit_cube();
+        half_spaces.push(HalfSpace::behind(Plain::from_plumb(&Vector(0.5, 0.5, 0.5), &Vector(1.0, 1.0, 1.0))));
+        match LinearProgramSolution::maximize(&Vector(1.0, 1.0, 2.0), &half_spaces) {
+            LinearProgramSolution::Optimal(point, active) => {
+                assert!((point * Vector(1.0, 1.0, 2.0) - 2.5).abs() < 0.00001);
+                assert!(active.contains(&6) && active.contains(&5));
+            },
+            other => panic!("Expected an optimal solution, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn region_without_vertices() {
+        // the slab 1 <= z <= 2 has no vertices, and the optimum is attained on a whole face
+        let half_spaces = [
+            HalfSpace::in_front_of(Plain::from_plumb(&Vector(0.0, 0.0, 1.0), &Vector(0.0, 0.0, 1.0))),
+            HalfSpace::behind(Plain::from_plumb(&Vector(0.0, 0.0, 2.0), &Vector(0.0, 0.0, 1.0)))
+        ];
+        match LinearProgramSolution::maximize(&Vector(0.0, 0.0, 1.0), &half_spaces) {
+            LinearProgramSolution::Optimal(point, active) => {
+                assert!((point.2 - 2.0).abs() < 0.00001);
+                assert_eq!(active, vec![1]);
+            },
+            other => panic!("Expected an optimal solution, got {:?}", other)
+        }
+        assert_eq!(LinearProgramSolution::maximize(&Vector(1.0, 0.0, 0.0), &half_spaces), LinearProgramSolution::Unbounded);
+    }
+
+    #[test]
+    fn infeasible() {
+        let mut half_spaces = unit_cube();
+        half_spaces.push(HalfSpace::in_front_of(Plain::from_plumb(&Vector(0.0, 0.0, 2.0), &Vector(0.0, 0.0, 1.0))));
+        assert_eq!(LinearProgramSolution::maximize(&Vector(1.0, 0.0, 0.0), &half_spaces), LinearProgramSolution::Infeasible);
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut half_spaces = unit_cube();
+        half_spaces.pop();
+        assert_eq!(LinearProgramSolution::maximize(&Vector(0.0, 0.0, 1.0), &half_spaces), LinearProgramSolution::Unbounded);
+        // the same region is bounded in the opposite direction
+        let solution = LinearProgramSolution::minimize(&Vector(0.0, 0.0, 1.0), &half_spaces);
+        assert!(matches!(solution, LinearProgramSolution::Optimal(Vector(_, _, z), _) if z == 0.0));
+        assert_eq!(LinearProgramSolution::maximize(&Vector(1.0, 0.0, 0.0), &[]), LinearProgramSolution::Unbounded);
+    }
+}
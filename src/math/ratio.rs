@@ -24,10 +24,7 @@ impl Ratio {
     }
 
     pub fn is_valid(self) -> bool {
-        match self {
-            Self::Invalid => false,
-            _ => true
-        }
+        !matches!(self, Self::Invalid)
     }
 }
 
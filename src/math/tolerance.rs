@@ -0,0 +1,32 @@
+//! Tolerance-aware comparisons of computed floating point values
+
+/// The largest absolute value still considered zero
+pub const EPSILON: f64 = 1e-9;
+
+/// Check if a computed value is zero, up to the tolerance
+pub fn is_zero(value: f64) -> bool {
+    value.abs() < EPSILON
+}
+
+/// Check if two computed values are equal, up to the tolerance
+pub fn approx_eq(a: f64, b: f64) -> bool {
+    is_zero(a - b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zero_within_tolerance() {
+        assert!(is_zero(0.0));
+        assert!(is_zero(1e-12));
+        assert!(!is_zero(1e-3));
+    }
+
+    #[test]
+    fn equality_within_tolerance() {
+        assert!(approx_eq(0.1 + 0.2, 0.3));
+        assert!(!approx_eq(0.1, 0.2));
+    }
+}
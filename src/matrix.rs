@@ -0,0 +1,283 @@
+//! 3x3 matrices acting on vectors in space
+
+use std::ops::{Add, Sub, Mul, Neg};
+
+use crate::{vector::Vector, math::tolerance};
+
+/// A 3x3 matrix, stored as its three rows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix3(pub Vector, pub Vector, pub Vector);
+
+impl Matrix3 {
+
+    /// The identity matrix, which maps every vector to itself
+    pub fn identity() -> Matrix3 {
+        Matrix3::diagonal(1.0, 1.0, 1.0)
+    }
+
+    /// A matrix scaling each axis separately
+    pub fn diagonal(x: f64, y: f64, z: f64) -> Matrix3 {
+        Matrix3(Vector(x, 0.0, 0.0), Vector(0.0, y, 0.0), Vector(0.0, 0.0, z))
+    }
+
+    /// Create a matrix from its rows
+    pub fn from_rows(row1: &Vector, row2: &Vector, row3: &Vector) -> Matrix3 {
+        Matrix3(*row1, *row2, *row3)
+    }
+
+    /// Create a matrix from its columns
+    pub fn from_columns(column1: &Vector, column2: &Vector, column3: &Vector) -> Matrix3 {
+        Matrix3(*column1, *column2, *column3).transpose()
+    }
+
+    /// The outer product `u * v^T`
+    pub fn outer_product(u: &Vector, v: &Vector) -> Matrix3 {
+        Matrix3(u.0 * *v, u.1 * *v, u.2 * *v)
+    }
+
+    /// The matrix of the vectoric product with a vector, i.e. `M * w = u x w`
+    pub fn cross_product_of(u: &Vector) -> Matrix3 {
+        let Vector(x, y, z) = *u;
+        Matrix3(Vector(0.0, -z, y), Vector(z, 0.0, -x), Vector(-y, x, 0.0))
+    }
+
+    /// The rotation by an angle about an axis through the origin, counterclockwise when looking against the axis.
+    /// # Panics:
+    /// - If the axis is the zero vector
+    pub fn rotation(axis: &Vector, angle: f64) -> Matrix3 {
+        if tolerance::is_zero(axis.length()) {
+            panic!("Cannot rotate about the zero vector")
+        }
+        // Rodrigues' formula: R = cos * I + sin * [k]x + (1 - cos) * k k^T
+        let unit = (1.0 / axis.length()) * *axis;
+        let (sin, cos) = angle.sin_cos();
+        cos * Matrix3::identity() + sin * Matrix3::cross_product_of(&unit) + (1.0 - cos) * Matrix3::outer_product(&unit, &unit)
+    }
+
+    /// The rows of the matrix
+    pub fn rows(&self) -> [Vector; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    /// The columns of the matrix
+    pub fn columns(&self) -> [Vector; 3] {
+        self.transpose().rows()
+    }
+
+    /// Swap the rows and the columns of the matrix
+    pub fn transpose(&self) -> Matrix3 {
+        let Matrix3(Vector(a, b, c), Vector(d, e, f), Vector(g, h, i)) = *self;
+        Matrix3(Vector(a, d, g), Vector(b, e, h), Vector(c, f, i))
+    }
+
+    /// The determinant, i.e. the signed volume scale of the matrix
+    pub fn determinant(&self) -> f64 {
+        Vector::triple_product(&self.0, &self.1, &self.2)
+    }
+
+    /// The sum of the diagonal entries
+    pub fn trace(&self) -> f64 {
+        self.0.0 + self.1.1 + self.2.2
+    }
+
+    /// The inverse matrix. Fails if the matrix is singular, i.e. its rows are linearly dependent
+    pub fn inverse(&self) -> Result<Matrix3, ()> {
+        if Vector::are_lindep(&self.0, &self.1, &self.2) {
+            return Err(());
+        }
+        // the columns of the inverse are the vectoric products of pairs of rows, divided by the determinant
+        let [row1, row2, row3] = self.rows();
+        let adjugate = Matrix3::from_columns(
+            &Vector::vectoric_product(&row2, &row3),
+            &Vector::vectoric_product(&row3, &row1),
+            &Vector::vectoric_product(&row1, &row2)
+        );
+        Ok((1.0 / self.determinant()) * adjugate)
+    }
+
+    /// Solve the linear system `M * x = b`. Fails if the matrix is singular
+    pub fn solve(&self, b: &Vector) -> Result<Vector, ()> {
+        Ok(self.inverse()? * *b)
+    }
+
+    /// The eigenvalues of a symmetric matrix in ascending order, and their unit eigenvectors,
+    /// computed with the cyclic Jacobi method. Only the upper triangle of the matrix is read
+    pub fn symmetric_eigen(&self) -> ([f64; 3], [Vector; 3]) {
+        let Matrix3(Vector(a11, a12, a13), Vector(_, a22, a23), Vector(_, _, a33)) = *self;
+        let mut a = [[a11, a12, a13], [a12, a22, a23], [a13, a23, a33]];
+        // the columns of v accumulate the rotations, and converge to the eigenvectors
+        let mut v = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let scale = a.iter().flatten().map(|entry| entry.abs()).fold(0.0, f64::max);
+        for _ in 0..50 {
+            let off_diagonal = a[0][1].abs() + a[0][2].abs() + a[1][2].abs();
+            if off_diagonal <= f64::EPSILON * scale {
+                break;
+            }
+            for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                // the rotation in the (p, q) plain that zeroes a[p][q]
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut() {
+                    let (x, y) = (row[p], row[q]);
+                    row[p] = c * x - s * y;
+                    row[q] = s * x + c * y;
+                }
+                let (row_p, row_q) = (a[p], a[q]);
+                a[p] = [0, 1, 2].map(|k| c * row_p[k] - s * row_q[k]);
+                a[q] = [0, 1, 2].map(|k| s * row_p[k] + c * row_q[k]);
+                for row in v.iter_mut() {
+                    let (x, y) = (row[p], row[q]);
+                    row[p] = c * x - s * y;
+                    row[q] = s * x + c * y;
+                }
+            }
+        }
+        let mut order = [0, 1, 2];
+        order.sort_by(|i, j| a[*i][*i].total_cmp(&a[*j][*j]));
+        (order.map(|i| a[i][i]), order.map(|i| Vector(v[0][i], v[1][i], v[2][i])))
+    }
+}
+
+impl Add for Matrix3 {
+    type Output = Self;
+
+    /// Entrywise addition of two matrices
+    fn add(self, rhs: Self) -> Self::Output {
+        Matrix3(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
+impl Sub for Matrix3 {
+    type Output = Self;
+
+    /// Entrywise subtraction of two matrices
+    fn sub(self, rhs: Self) -> Self::Output {
+        Matrix3(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
+    }
+}
+
+impl Neg for Matrix3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Matrix3(-self.0, -self.1, -self.2)
+    }
+}
+
+impl Mul<Vector> for Matrix3 {
+    type Output = Vector;
+
+    /// Apply the matrix to a column vector
+    fn mul(self, rhs: Vector) -> Self::Output {
+        Vector(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl Mul for Matrix3 {
+    type Output = Self;
+
+    /// Compose two matrices, applying the right one first
+    fn mul(self, rhs: Self) -> Self::Output {
+        let columns = rhs.transpose();
+        Matrix3(columns * self.0, columns * self.1, columns * self.2)
+    }
+}
+
+/// multiply a matrix by a scalar
+impl Mul<Matrix3> for f64 {
+    type Output = Matrix3;
+
+    fn mul(self, rhs: Matrix3) -> Self::Output {
+        Matrix3(self * rhs.0, self * rhs.1, self * rhs.2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close};
+
+    fn assert_matrices_close(m: &Matrix3, n: &Matrix3) {
+        for (u, v) in m.rows().iter().zip(n.rows().iter()) {
+            assert_close(u, v);
+        }
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let (u, v, w) = (Vector(1.0, 2.0, 3.0), Vector(4.0, 5.0, 6.0), Vector(7.0, 8.0, 10.0));
+        let m = Matrix3::from_columns(&u, &v, &w);
+        assert_eq!(m.columns(), [u, v, w]);
+        assert_eq!(m.transpose(), Matrix3::from_rows(&u, &v, &w));
+        assert_eq!(m * Vector(1.0, 0.0, 0.0), u);
+        assert_eq!(m.trace(), 16.0);
+        assert_eq!(Matrix3::outer_product(&u, &v) * w, (v * w) * u);
+        assert_eq!(Matrix3::cross_product_of(&u) * v, Vector::vectoric_product(&u, &v));
+    }
+
+    #[test]
+    fn determinant_and_inverse() {
+        let m = Matrix3(Vector(2.0, 0.0, 1.0), Vector(1.0, 3.0, 0.0), Vector(0.0, 1.0, 4.0));
+        assert!((m.determinant() - 25.0).abs() < EPSILON);
+        let inverse = m.inverse().unwrap();
+        assert_matrices_close(&(m * inverse), &Matrix3::identity());
+        assert_matrices_close(&(inverse * m), &Matrix3::identity());
+        assert!(((m * m).determinant() - 625.0).abs() < EPSILON);
+
+        let b = Vector(1.0, 2.0, 3.0);
+        assert_close(&(m * m.solve(&b).unwrap()), &b);
+
+        let singular = Matrix3(Vector(1.0, 2.0, 3.0), Vector(2.0, 4.0, 6.0), Vector(0.0, 1.0, 4.0));
+        assert_eq!(singular.inverse(), Err(()));
+        assert_eq!(singular.solve(&b), Err(()));
+
+        // a small scale is not singular
+        assert_matrices_close(&Matrix3::diagonal(1e-4, 1e-4, 1e-4).inverse().unwrap(), &Matrix3::diagonal(1e4, 1e4, 1e4));
+    }
+
+    #[test]
+    fn rotation() {
+        let quarter = Matrix3::rotation(&Vector(0.0, 0.0, 2.0), PI / 2.0);
+        assert_close(&(quarter * Vector(1.0, 0.0, 0.0)), &Vector(0.0, 1.0, 0.0));
+        assert!((quarter.determinant() - 1.0).abs() < EPSILON);
+        assert_matrices_close(&quarter.inverse().unwrap(), &quarter.transpose());
+
+        let axis = Vector(1.0, 1.0, 1.0);
+        let third = Matrix3::rotation(&axis, 2.0 * PI / 3.0);
+        assert_close(&(third * Vector(1.0, 0.0, 0.0)), &Vector(0.0, 1.0, 0.0));
+        assert_close(&(third * axis), &axis);
+        assert_matrices_close(&(third * third * third), &Matrix3::identity());
+        // the trace of a rotation is 1 + 2cos(angle)
+        assert!((third.trace() - (1.0 + 2.0 * (2.0 * PI / 3.0).cos())).abs() < EPSILON);
+    }
+
+    #[test]
+    fn symmetric_eigen_decomposition() {
+        let m = Matrix3(Vector(4.0, 1.0, -2.0), Vector(1.0, 2.0, 0.0), Vector(-2.0, 0.0, 3.0));
+        let (values, vectors) = m.symmetric_eigen();
+        assert!(values[0] <= values[1] && values[1] <= values[2]);
+        assert!((values.iter().sum::<f64>() - m.trace()).abs() < EPSILON);
+        assert!((values.iter().product::<f64>() - m.determinant()).abs() < EPSILON);
+        for (value, vector) in values.iter().zip(vectors.iter()) {
+            assert!((vector.length() - 1.0).abs() < EPSILON);
+            assert_close(&(m * *vector), &(*value * *vector));
+        }
+
+        let (values, _) = Matrix3::diagonal(3.0, -1.0, 2.0).symmetric_eigen();
+        assert_eq!(values, [-1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotation_about_zero_vector() {
+        Matrix3::rotation(&Vector(0.0, 0.0, 0.0), 1.0);
+    }
+}
@@ -2,15 +2,19 @@
 
 pub mod line_relations;
 pub mod relations;
-mod intersection;
+pub mod side;
+pub mod half_space;
+pub mod basis;
 
 use std::f64::consts::PI;
 
-use crate::{vector::Vector, line::{Line, relations::LineRelations}, math::equation::EquationSolution};
+use crate::{vector::Vector, matrix::Matrix3, line::{Line, relations::LineRelations}, math::{equation::EquationSolution, tolerance}};
 
 use self::line_relations::PlainLineRelations;
+use self::side::Side;
+use self::basis::PlainBasis;
 
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub struct Plain {
     plumb: Vector,
     constant_d: f64
@@ -26,10 +30,20 @@ impl Plain {
         }
         let plumb = Vector::vectoric_product(dir1, dir2);
         // we went plumb * origin + d = 0, therefore d = -1 * plumb + origin
-        let constant_d = -1.0 * (plumb * (*origin));
+        let constant_d = -(plumb * (*origin));
         Plain { plumb, constant_d }
     }
 
+    /// Generates a plain through a point, perpendicular to a given plumb
+    /// # Panics:
+    /// - If the plumb is the zero vector
+    pub fn from_plumb(origin: &Vector, plumb: &Vector) -> Plain {
+        if tolerance::is_zero(plumb.length()) {
+            panic!("The zero vector is not perpendicular to a plane")
+        }
+        Plain { plumb: *plumb, constant_d: -(*plumb * (*origin)) }
+    }
+
     /// Generates a plain from intersecting or parallel lines
     /// # Panics:
     /// - If the two lines provided are not intersecting and cannot form a plain
@@ -56,27 +70,133 @@ impl Plain {
         Plain::new(point1, &dir1, &dir2)
     }
 
+    /// Find the common plain of a set of points.
+    /// Returns None if the points are not coplanar, or if they are collinear and don't determine a single plain
+    pub fn through_points(points: &[Vector]) -> Option<Plain> {
+        let origin = *points.first()?;
+        // use the points that span the largest triangle, to reduce rounding errors
+        let farthest = *points.iter()
+            .max_by(|p1, p2| (**p1 - origin).length().total_cmp(&(**p2 - origin).length()))?;
+        let axis = Line::from_two_points(origin, &farthest);
+        if tolerance::is_zero(axis.direction.length()) {
+            return None;
+        }
+        let third = *points.iter()
+            .max_by(|p1, p2| axis.distance_from_point(p1).total_cmp(&axis.distance_from_point(p2)))?;
+        if axis.is_on_line(&third) {
+            return None;
+        }
+
+        let plain = Plain::from_three_points(&origin, &farthest, &third);
+        if points.iter().all(|point| plain.contains_point(point)) { Some(plain) } else { None }
+    }
+
+    /// Check if all of the given points lie on a single plain
+    pub fn are_coplanar(points: &[Vector]) -> bool {
+        Line::are_collinear(points) || Plain::through_points(points).is_some()
+    }
+
+    /// Find the common plain of a set of lines.
+    /// Returns None if the lines are not coplanar, or if they all unite and don't determine a single plain
+    pub fn through_lines(lines: &[Line]) -> Option<Plain> {
+        Plain::through_points(&Plain::points_of_lines(lines))
+    }
+
+    /// Check if all of the given lines lie on a single plain
+    pub fn are_coplanar_lines(lines: &[Line]) -> bool {
+        Plain::are_coplanar(&Plain::points_of_lines(lines))
+    }
+
+    /// Two points on each line, such that the lines are coplanar exactly when the points are
+    fn points_of_lines(lines: &[Line]) -> Vec<Vector> {
+        lines.iter()
+            .flat_map(|line| [line.point, line.point + line.direction])
+            .collect()
+    }
+
     fn compute(&self, point: &Vector) -> f64 {
         self.plumb * (*point) + self.constant_d
     }
 
     /// Calculate distance between a given point and this plain
     pub fn distance_from(&self, point: &Vector) -> f64 {
-        self.compute(point).abs() / self.plumb.length()
+        self.signed_distance_from(point).abs()
+    }
+
+    /// Calculate the signed distance between a given point and this plain.
+    /// Points on the side the plumb points to have a positive distance, points behind it a negative one.
+    pub fn signed_distance_from(&self, point: &Vector) -> f64 {
+        self.compute(point) / self.plumb.length()
+    }
+
+    /// Find the point of the plain closest to a given point
+    pub fn projection_of(&self, point: &Vector) -> Vector {
+        *point - (self.compute(point) / (self.plumb * self.plumb)) * self.plumb
+    }
+
+    /// Classify the side of the plain a given point is on
+    pub fn side_of(&self, point: &Vector) -> Side {
+        Side::of(self.signed_distance_from(point))
+    }
+
+    /// Classify the side of the plain each of the given points is on
+    pub fn classify(&self, points: &[Vector]) -> Vec<Side> {
+        points.iter().map(|point| self.side_of(point)).collect()
+    }
+
+    /// Check if two points lie strictly on the same side of the plain.
+    /// A point on the plain is on no side of it.
+    pub fn same_side(&self, point1: &Vector, point2: &Vector) -> bool {
+        match (self.side_of(point1), self.side_of(point2)) {
+            (Side::On, _) | (_, Side::On) => false,
+            (side1, side2) => side1 == side2
+        }
+    }
+
+    /// The same plain, with the opposite orientation
+    pub fn flipped(&self) -> Plain {
+        Plain { plumb: -self.plumb, constant_d: -self.constant_d }
+    }
+
+    /// Compute the points where the plain crosses the x, y and z axes, as their coordinate on the axis.
+    /// An axis that is parallel to the plain, or contained in it, has no single crossing point
+    pub fn axis_intercepts(&self) -> (Option<f64>, Option<f64>, Option<f64>) {
+        let intercept = |coefficient: f64| match EquationSolution::compute(coefficient, self.constant_d) {
+            EquationSolution::Real(value) => Some(value),
+            _ => None
+        };
+        let Vector(a, b, c) = self.plumb;
+        (intercept(a), intercept(b), intercept(c))
+    }
+
+    /// Compute the volume of the pyramid the plain cuts off the coordinate octant it crosses,
+    /// bounded by the plain and the three coordinate planes.
+    /// Returns None if the plain is parallel to an axis, and the cut-off region is not bounded
+    pub fn octant_pyramid_volume(&self) -> Option<f64> {
+        let Vector(a, b, c) = self.plumb;
+        if a == 0.0 || b == 0.0 || c == 0.0 {
+            return None;
+        }
+        // the intercepts are -d/a, -d/b and -d/c, and the volume is |xyz|/6 = |d^3/abc|/6
+        let d = self.constant_d;
+        Some((d * d * d / (a * b * c)).abs() / 6.0)
     }
 
     /// Check if the plain contains a given point
     pub fn contains_point(&self, point: &Vector) -> bool {
-        self.compute(point) == 0.0
+        self.side_of(point) == Side::On
     }
 
-    /// Check if a plain contains a given line
+    /// Check if a plain contains a given line, up to the tolerance.
+    /// A line with a zero direction is treated as its single point
     pub fn contains_line(&self, line: &Line) -> bool {
         // Point is on line, and the direction of the line is vertical to the plumb
-        self.contains_point(&line.point) && (line.direction * self.plumb == 0.0) 
+        let scale = line.direction.length() * self.plumb.length();
+        self.contains_point(&line.point) && (scale == 0.0 || tolerance::is_zero(line.direction * self.plumb / scale))
     }
 
-    /// Compute the angle between the plain and a given vector
+    /// Compute the signed angle (-PI/2 <= x <= PI/2) between the plain and a given vector.
+    /// The angle is positive when the vector points to the side of the plumb, and negative when it points away from it
     pub fn angle_with_vector(&self, vector: &Vector) -> f64 {
         (PI / 2.0) - Vector::angle_between(&self.plumb, vector)
     }
@@ -98,6 +218,36 @@ impl Plain {
         }
     }
 
+    /// A unit vector perpendicular to the plain, in the direction of its plumb
+    pub fn unit_normal(&self) -> Vector {
+        (1.0 / self.plumb.length()) * self.plumb
+    }
+
+    /// An orthonormal basis of the plain, from the point of it closest to the origin
+    pub fn basis(&self) -> PlainBasis {
+        PlainBasis::new(self, &Vector(0.0, 0.0, 0.0))
+    }
+
+    /// An orthonormal basis of the plain, from the projection of a given point onto it
+    pub fn basis_at(&self, origin: &Vector) -> PlainBasis {
+        PlainBasis::new(self, origin)
+    }
+
+    /// Compute the angle (0 <= x <= PI) between the plumbs of two oriented plains
+    pub fn oriented_angle_between(plain1: &Plain, plain2: &Plain) -> f64 {
+        let (normal1, normal2) = (plain1.unit_normal(), plain2.unit_normal());
+        Vector::vectoric_product(&normal1, &normal2).length().atan2(normal1 * normal2)
+    }
+
+    /// Compute the signed angle (-PI < x <= PI) rotating the plumb of the first plain to the plumb of the second,
+    /// counterclockwise around a reference axis by the right hand rule.
+    /// The axis should be perpendicular to both plumbs, such as the direction of their intersection line
+    pub fn signed_angle_between(plain1: &Plain, plain2: &Plain, axis: &Vector) -> f64 {
+        let (normal1, normal2) = (plain1.unit_normal(), plain2.unit_normal());
+        let sine = Vector::vectoric_product(&normal1, &normal2) * (*axis) / axis.length();
+        sine.atan2(normal1 * normal2)
+    }
+
     /// Compute the relation between a plane and a line
     pub fn relation_with_line(&self, line: &Line) -> PlainLineRelations {
         // Find a point on the line, p1 = p + tu, such that compute(p1) == 0
@@ -137,6 +287,13 @@ impl Plain {
         difference / plumb1.length()
     }
 
+    /// Compute the single common point of three planes, if existing
+    pub fn intersection_point_of(plain1: &Plain, plain2: &Plain, plain3: &Plain) -> Option<Vector> {
+        // the plumbs are the rows of the system ni * x = -di
+        let system = Matrix3(plain1.plumb, plain2.plumb, plain3.plumb);
+        system.solve(&Vector(-plain1.constant_d, -plain2.constant_d, -plain3.constant_d)).ok()
+    }
+
     /// Compute an intersection line of two planes
     /// # Panics:
     /// - If the two planes unite or parallel and therefore cannot form a line
@@ -145,23 +302,15 @@ impl Plain {
             panic!("The two planes can't be parallel or uniting to calculate the intersection between them")
         }
 
-        // given two plains: 
-        // ax + by + cz + d1 = 0
-        // mx + ny + kz + d2 = 0
-        // the target is to find an equation for two of the three dimensions using the third one.
-        // such as: z = 2x + 4, y = 3x + 8
-        // then, we substitute x=0, x=1 and we have two points that we can use to form a line.
-        
-        // special case: two of the params a,b,c (or m,n,k) is 0
-        // i.e z = 4, subsitute immediately without solving
-
-        // special case: one of the params a,b,c (or m,n,k) is 0
-        // i.e: x + y + 3 = 0 => y = -x - 3, no need to solve a special equation.
-
-        // default case: none of the params is 0 (all values being 0 is not mathematically possible)
-        // i.e: x + y + z + 4 = 0, 2x - 3y + 5z - 8 = 0
-
-        Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0))
+        // the line is perpendicular to both plumbs: u = n1 x n2
+        // its point closest to the origin is a combination p = a(n2 x u) + b(u x n1),
+        // since n1 * (n2 x u) = n2 * (u x n1) = |u|^2 and the other products vanish,
+        // n1 * p + d1 = 0 gives a = -d1/|u|^2, and n2 * p + d2 = 0 gives b = -d2/|u|^2
+        let direction = Vector::vectoric_product(&plain1.plumb, &plain2.plumb);
+        let combination = -plain1.constant_d * Vector::vectoric_product(&plain2.plumb, &direction)
+            - plain2.constant_d * Vector::vectoric_product(&direction, &plain1.plumb);
+        let point = (1.0 / (direction * direction)) * combination;
+        Line::new(point, direction)
     }
 
 
@@ -184,6 +333,13 @@ mod tests {
         Plain::new(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(2.0, 0.0, 0.0));
     }
 
+    #[test]
+    fn creates_new_from_plumb() {
+        let plain = Plain::from_plumb(&Vector(1.0, 2.0, 3.0), &Vector(0.0, 0.0, 2.0)); // z=3
+        assert!(plain.contains_point(&Vector(5.0, -1.0, 3.0)));
+        assert_eq!(plain.unit_normal(), Vector(0.0, 0.0, 1.0));
+    }
+
     #[test]
     fn creates_new_from_intersection() {
         let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)); // the x axis
@@ -223,6 +379,43 @@ mod tests {
         Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(3.0, 0.0, 0.0)); // z=0
     }
 
+    #[test]
+    fn coplanar_points() {
+        let points = [Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0), Vector(1.0, 1.0, -1.0)];
+        let plain = Plain::through_points(&points).unwrap();
+        assert!(plain.contains_point(&Vector(0.5, 0.5, 0.0)));
+        assert!(Plain::are_coplanar(&points));
+        let collinear = [Vector(1.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0), Vector(3.0, 0.0, 0.0)];
+        assert!(Plain::through_points(&collinear).is_none());
+        assert!(Plain::are_coplanar(&collinear));
+    }
+
+    #[test]
+    fn non_coplanar_points() {
+        let points = [Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0)];
+        assert!(Plain::through_points(&points).is_none());
+        assert!(!Plain::are_coplanar(&points));
+    }
+
+    #[test]
+    fn coplanar_lines() {
+        let lines = [
+            Line::new(Vector(0.0, 0.0, 2.0), Vector(1.0, 0.0, 0.0)),
+            Line::new(Vector(0.0, 0.0, 2.0), Vector(0.0, 1.0, 0.0)),
+            Line::new(Vector(5.0, 5.0, 2.0), Vector(1.0, 1.0, 0.0)),
+            Line::new(Vector(1.0, 0.0, 2.0), Vector(0.0, 3.0, 0.0)),
+        ];
+        let plain = Plain::through_lines(&lines).unwrap();
+        assert_eq!(plain.distance_from(&Vector(0.0, 0.0, 0.0)), 2.0);
+        assert!(Plain::are_coplanar_lines(&lines));
+
+        let foreign = [lines[0], Line::new(Vector(0.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0))];
+        assert!(!Plain::are_coplanar_lines(&foreign));
+        let uniting = [lines[0], Line::new(Vector(4.0, 0.0, 2.0), Vector(-2.0, 0.0, 0.0))];
+        assert!(Plain::through_lines(&uniting).is_none());
+        assert!(Plain::are_coplanar_lines(&uniting));
+    }
+
     #[test]
     fn contains_point() {
         let origin = Vector(0.0,0.0,0.0);
@@ -242,6 +435,20 @@ mod tests {
         assert!(!plain.contains_line(&line3));
     }
 
+    #[test]
+    fn contains_line_within_tolerance() {
+        let origin = Vector(0.0, 0.0, 0.0);
+        let plain = Plain::new(&origin, &Vector(1.0, -1.0, 0.0), &Vector(0.0, 1.0, -1.0)); // x+y+z=0
+        // a direction computed with rounding errors, nearly perpendicular to the plumb
+        let direction = Vector(0.1 + 0.2, -0.3, 0.0);
+        assert_ne!(direction * Vector(1.0, 1.0, 1.0), 0.0);
+        assert!(plain.contains_line(&Line::new(origin, direction)));
+        assert!(!plain.contains_line(&Line::new(origin, Vector(1.0, 0.0, 0.0))));
+        // a zero direction is a single point
+        assert!(plain.contains_line(&Line::new(Vector(1.0, -1.0, 0.0), origin)));
+        assert!(!plain.contains_line(&Line::new(Vector(1.0, 1.0, 0.0), origin)));
+    }
+
     #[test]
     fn distance_from_points() {
         let origin = Vector(0.0,0.0,0.0);
@@ -250,6 +457,70 @@ mod tests {
         assert_eq!(plain.distance_from(&Vector(0.0, 0.0, 1.0)), 1.0);
     }
 
+    #[test]
+    fn signed_distance_from_points() {
+        let plain = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
+        assert_eq!(plain.signed_distance_from(&Vector(1.0, 2.0, 3.0)), 3.0);
+        assert_eq!(plain.signed_distance_from(&Vector(1.0, 2.0, -3.0)), -3.0);
+        assert_eq!(plain.flipped().signed_distance_from(&Vector(1.0, 2.0, 3.0)), -3.0);
+    }
+
+    #[test]
+    fn projection_of_point() {
+        let plain = Plain::from_three_points(&Vector(0.0,0.0,1.0), &Vector(1.0, 0.0, 1.0), &Vector(0.0, 1.0, 1.0)); // z=1
+        assert_eq!(plain.projection_of(&Vector(2.0, 3.0, -4.0)), Vector(2.0, 3.0, 1.0));
+    }
+
+    #[test]
+    fn classifies_sides() {
+        let plain = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
+        let points = [Vector(0.0, 0.0, 1.0), Vector(5.0, 5.0, -1.0), Vector(3.0, 2.0, 0.0)];
+        assert_eq!(plain.classify(&points), vec![Side::Front, Side::Back, Side::On]);
+        assert!(plain.same_side(&Vector(0.0, 0.0, 1.0), &Vector(4.0, 1.0, 2.0)));
+        assert!(!plain.same_side(&Vector(0.0, 0.0, 1.0), &Vector(4.0, 1.0, -2.0)));
+        assert!(!plain.same_side(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn intersection_line_of_plains() {
+        let plain1 = Plain::from_three_points(&Vector(0.0,0.0,1.0), &Vector(1.0, 0.0, 1.0), &Vector(0.0, 1.0, 1.0)); // z=1
+        let plain2 = Plain::from_three_points(&Vector(2.0,0.0,0.0), &Vector(2.0, 1.0, 0.0), &Vector(2.0, 0.0, 1.0)); // x=2
+        let line = Plain::intersection_line_of(&plain1, &plain2);
+        assert_eq!(line, Line::new(Vector(2.0, 5.0, 1.0), Vector(0.0, 1.0, 0.0)));
+        assert!(plain1.contains_line(&line) && plain2.contains_line(&line));
+
+        let slanted = Plain::from_three_points(&Vector(1.0,0.0,0.0), &Vector(0.0, 2.0, 0.0), &Vector(0.0, 0.0, 3.0));
+        let line = Plain::intersection_line_of(&plain1, &slanted);
+        assert!(plain1.contains_point(&line.point) && slanted.contains_point(&line.point));
+        assert!(plain1.contains_point(&(line.point + line.direction)) && slanted.contains_point(&(line.point + line.direction)));
+    }
+
+    #[test]
+    fn intersection_point_of_plains() {
+        let plain1 = Plain::from_three_points(&Vector(0.0,0.0,1.0), &Vector(1.0, 0.0, 1.0), &Vector(0.0, 1.0, 1.0)); // z=1
+        let plain2 = Plain::from_three_points(&Vector(2.0,0.0,0.0), &Vector(2.0, 1.0, 0.0), &Vector(2.0, 0.0, 1.0)); // x=2
+        let plain3 = Plain::from_three_points(&Vector(1.0,0.0,0.0), &Vector(0.0, 1.0, 0.0), &Vector(0.0, 0.0, 1.0)); // x+y+z=1
+        assert_eq!(Plain::intersection_point_of(&plain1, &plain2, &plain3).unwrap(), Vector(2.0, -2.0, 1.0));
+        let parallel = Plain::from_three_points(&Vector(0.0,0.0,3.0), &Vector(1.0, 0.0, 3.0), &Vector(0.0, 1.0, 3.0)); // z=3
+        assert!(Plain::intersection_point_of(&plain1, &plain2, &parallel).is_none());
+    }
+
+    #[test]
+    fn octant_pyramid() {
+        // x/2 + y/3 + z/4 = 1
+        let plain = Plain::from_three_points(&Vector(2.0, 0.0, 0.0), &Vector(0.0, 3.0, 0.0), &Vector(0.0, 0.0, 4.0));
+        let (x, y, z) = plain.axis_intercepts();
+        assert_eq!((x.unwrap(), y.unwrap(), z.unwrap()), (2.0, 3.0, 4.0));
+        assert_eq!(plain.octant_pyramid_volume().unwrap(), 4.0);
+
+        let through_origin = Plain::from_three_points(&Vector(0.0, 0.0, 0.0), &Vector(1.0, -1.0, 0.0), &Vector(1.0, 0.0, -1.0));
+        assert_eq!(through_origin.octant_pyramid_volume().unwrap(), 0.0);
+
+        let parallel_to_z = Plain::new(&Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0), &Vector(1.0, -1.0, 0.0));
+        assert_eq!(parallel_to_z.axis_intercepts().2, None);
+        assert!(parallel_to_z.octant_pyramid_volume().is_none());
+    }
+
     #[test]
     fn distance_between_plains() {
         let plain1 = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
@@ -268,6 +539,28 @@ mod tests {
         assert!(plain.angle_with_line(&line3) - PI / 4.0 < EPSILON);
     }
 
+    #[test]
+    fn oriented_angles_between_plains() {
+        const EPSILON: f64 = 0.0001;
+        let xy = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0, plumb +z
+        let slanted = Plain::new(&Vector(0.0,0.0,0.0), &Vector(0.0, 1.0, 0.0), &Vector(1.0, 0.0, 1.0)); // z=x, plumb (1, 0, -1)
+        assert!((Plain::oriented_angle_between(&xy, &slanted) - 3.0 * PI / 4.0).abs() < EPSILON);
+        assert!((Plain::oriented_angle_between(&xy, &slanted.flipped()) - PI / 4.0).abs() < EPSILON);
+        assert_eq!(Plain::oriented_angle_between(&xy, &xy.flipped()), PI);
+
+        let y_axis = Vector(0.0, 1.0, 0.0);
+        assert!((Plain::signed_angle_between(&xy, &slanted, &y_axis) - 3.0 * PI / 4.0).abs() < EPSILON);
+        assert!((Plain::signed_angle_between(&slanted, &xy, &y_axis) + 3.0 * PI / 4.0).abs() < EPSILON);
+        assert!((Plain::signed_angle_between(&xy, &slanted, &(-y_axis)) + 3.0 * PI / 4.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn signed_angle_with_vector() {
+        let xy = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0, plumb +z
+        assert_eq!(xy.angle_with_vector(&Vector(0.0, 0.0, 2.0)), PI / 2.0);
+        assert_eq!(xy.angle_with_vector(&Vector(0.0, 0.0, -2.0)), -PI / 2.0);
+    }
+
     #[test]
     fn angle_between_plains() {
         let line1 = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0)); // the x axis
@@ -0,0 +1,69 @@
+//! Orthonormal bases of plains, giving 2D coordinates to the points in them
+
+use crate::{vector::Vector, frame::Frame};
+
+use super::Plain;
+
+/// Two orthogonal unit axes in a plain and its unit normal, from an origin in the plain.
+/// The basis is right-handed: the vectoric product of the axes is the normal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlainBasis {
+    pub origin: Vector,
+    pub u: Vector,
+    pub v: Vector,
+    pub normal: Vector
+}
+
+impl PlainBasis {
+
+    /// A basis of a plain, from the projection of a given point onto it
+    pub fn new(plain: &Plain, origin: &Vector) -> PlainBasis {
+        let normal = plain.unit_normal();
+        let u = normal.any_perpendicular();
+        let v = Vector::vectoric_product(&normal, &u);
+        PlainBasis { origin: plain.projection_of(origin), u, v, normal }
+    }
+
+    /// The (u, v) coordinates of a point projected onto the plain
+    pub fn coordinates_of(&self, point: &Vector) -> (f64, f64) {
+        let offset = *point - self.origin;
+        (offset * self.u, offset * self.v)
+    }
+
+    /// The point of the plain with given (u, v) coordinates
+    pub fn point_at(&self, u: f64, v: f64) -> Vector {
+        self.origin + u * self.u + v * self.v
+    }
+
+    /// The frame of the basis, where the plain is the z = 0 plain of the local coordinates
+    pub fn frame(&self) -> Frame {
+        Frame::new(&self.origin, &self.u, &self.v, &self.normal).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn maps_points_to_coordinates_and_back() {
+        let plain = Plain::from_three_points(&Vector(1.0, 0.0, 0.0), &Vector(0.0, 2.0, 0.0), &Vector(0.0, 0.0, 3.0));
+        let basis = plain.basis();
+        assert!(plain.contains_point(&basis.origin));
+        assert!((basis.u * basis.v).abs() < EPSILON && (basis.u * basis.normal).abs() < EPSILON);
+        assert!((Vector::vectoric_product(&basis.u, &basis.v) - basis.normal).length() < EPSILON);
+
+        let point = Vector(1.0, 0.0, 0.0);
+        let (u, v) = basis.coordinates_of(&point);
+        assert!((basis.point_at(u, v) - point).length() < EPSILON);
+        // a point off the plain maps to its projection
+        let (u, v) = basis.coordinates_of(&(point + 2.0 * basis.normal));
+        assert!((basis.point_at(u, v) - point).length() < EPSILON);
+
+        let anchored = plain.basis_at(&point);
+        assert_eq!(anchored.coordinates_of(&point), (0.0, 0.0));
+        let local = anchored.frame().point_to_local(&(point + basis.v));
+        assert!((local - Vector(0.0, 1.0, 0.0)).length() < EPSILON);
+    }
+}
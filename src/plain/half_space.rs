@@ -0,0 +1,113 @@
+//! Represents half-spaces bounded by plains
+
+use std::f64::consts::PI;
+
+use crate::vector::Vector;
+
+use super::{Plain, side::Side};
+
+/// The part of space on one side of a plain, including the plain itself.
+/// The region is kept behind its boundary: the plumb of the boundary points out of it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HalfSpace {
+    boundary: Plain
+}
+
+impl HalfSpace {
+    /// Generates the half-space behind a plain, opposite to its plumb
+    pub fn behind(boundary: Plain) -> HalfSpace {
+        HalfSpace { boundary }
+    }
+
+    /// Generates the half-space in front of a plain, on the side its plumb points to
+    pub fn in_front_of(boundary: Plain) -> HalfSpace {
+        HalfSpace { boundary: boundary.flipped() }
+    }
+
+    /// Generates the half-space bounded by a plain that contains a given point
+    /// # Panics:
+    /// - If the point is on the plain, and does not determine a side
+    pub fn containing(boundary: Plain, point: &Vector) -> HalfSpace {
+        match boundary.side_of(point) {
+            Side::Back => Self::behind(boundary),
+            Side::Front => Self::in_front_of(boundary),
+            Side::On => panic!("A point on the boundary does not determine a half-space")
+        }
+    }
+
+    /// The boundary plain, with its plumb pointing out of the half-space
+    pub fn boundary(&self) -> &Plain {
+        &self.boundary
+    }
+
+    /// The half-space on the other side of the boundary
+    pub fn complement(&self) -> HalfSpace {
+        Self::in_front_of(self.boundary)
+    }
+
+    /// Check if the half-space contains a given point. Points on the boundary are contained.
+    pub fn contains(&self, point: &Vector) -> bool {
+        self.boundary.side_of(point) != Side::Front
+    }
+
+    /// Compute the angle (0 < x < PI) of the wedge two intersecting half-spaces bound together
+    pub fn wedge_angle(half_space1: &HalfSpace, half_space2: &HalfSpace) -> f64 {
+        let angle = Plain::angle_between(&half_space1.boundary, &half_space2.boundary);
+        // outward plumbs pointing to a similar direction bound an obtuse wedge
+        if half_space1.boundary.plumb * half_space2.boundary.plumb > 0.0 {
+            PI - angle
+        } else {
+            angle
+        }
+    }
+
+    /// Check if the half-space contains all of the given points
+    pub fn contains_all(&self, points: &[Vector]) -> bool {
+        points.iter().all(|point| self.contains(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xy_plain;
+
+    #[test]
+    fn contains_points() {
+        let below = HalfSpace::behind(xy_plain());
+        assert!(below.contains(&Vector(1.0, 1.0, -1.0)));
+        assert!(below.contains(&Vector(1.0, 1.0, 0.0)));
+        assert!(!below.contains(&Vector(1.0, 1.0, 1.0)));
+
+        let above = HalfSpace::in_front_of(xy_plain());
+        assert!(above.contains(&Vector(1.0, 1.0, 1.0)));
+        assert!(!above.contains(&Vector(1.0, 1.0, -1.0)));
+        assert_eq!(above.complement(), below);
+    }
+
+    #[test]
+    fn oriented_by_point() {
+        let half_space = HalfSpace::containing(xy_plain(), &Vector(0.0, 0.0, 5.0));
+        assert!(half_space.contains_all(&[Vector(3.0, 0.0, 1.0), Vector(0.0, 0.0, 0.0)]));
+        assert!(!half_space.contains_all(&[Vector(3.0, 0.0, 1.0), Vector(0.0, 0.0, -1.0)]));
+    }
+
+    #[test]
+    fn wedge_angles() {
+        let below = HalfSpace::behind(xy_plain());
+        let yz_plain = Plain::from_three_points(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), &Vector(0.0, 0.0, 1.0)); // x=0
+        let slanted = Plain::from_three_points(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), &Vector(1.0, 0.0, 1.0)); // z=x
+        assert_eq!(HalfSpace::wedge_angle(&below, &HalfSpace::containing(yz_plain, &Vector(1.0, 0.0, 0.0))), PI / 2.0);
+        // below z=0, the half-space z >= x makes an acute wedge and z <= x an obtuse one
+        let acute = HalfSpace::containing(slanted, &Vector(-1.0, 0.0, 0.0));
+        let obtuse = HalfSpace::containing(slanted, &Vector(1.0, 0.0, 0.0));
+        assert!((HalfSpace::wedge_angle(&below, &acute) - PI / 4.0).abs() < 0.00001);
+        assert!((HalfSpace::wedge_angle(&below, &obtuse) - 3.0 * PI / 4.0).abs() < 0.00001);
+    }
+
+    #[test]
+    #[should_panic]
+    fn oriented_by_point_on_boundary() {
+        HalfSpace::containing(xy_plain(), &Vector(1.0, 1.0, 0.0));
+    }
+}
@@ -1,5 +1,5 @@
 //! Represents a relation between two planes
-use crate::{line::Line, vector::Vector};
+use crate::line::Line;
 
 use super::Plain;
 
@@ -18,7 +18,7 @@ impl PlainRelations {
         if !plain1.plumb.is_lindep(&plain2.plumb) {
             // planes intersect.
             let angle = Plain::angle_between(plain1, plain2);
-            let intersection = Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0));
+            let intersection = Plain::intersection_line_of(plain1, plain2);
             return Self::Intersect(intersection, angle);
         }
 
@@ -28,6 +28,10 @@ impl PlainRelations {
 
 #[cfg(test)]
 mod tests {
+    use std::f64::consts::PI;
+
+    use crate::vector::Vector;
+
     use super::*;
 
 
@@ -39,6 +43,15 @@ mod tests {
         assert_eq!(PlainRelations::of(&plain1, &plain2), PlainRelations::Unite)
     }
 
+    #[test]
+    fn intersecting_plains() {
+        let origin = Vector(0.0,0.0,0.0);
+        let plain1 = Plain::from_three_points(&origin, &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
+        let plain2 = Plain::from_three_points(&origin, &Vector(1.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)); // y=0
+        let x_axis = Line::new(origin, Vector(1.0, 0.0, 0.0));
+        assert_eq!(PlainRelations::of(&plain1, &plain2), PlainRelations::Intersect(x_axis, PI / 2.0))
+    }
+
     #[test]
     fn parallel_plains() {
         let plain1 = Plain::from_three_points(&Vector(0.0,0.0,0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
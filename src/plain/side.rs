@@ -0,0 +1,55 @@
+//! Classify points by the side of a plain they are on
+
+use crate::math::tolerance;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    /// The point is on the side the plumb of the plain points to
+    Front,
+    /// The point is on the side opposite to the plumb of the plain
+    Back,
+    /// The point is on the plain itself
+    On
+}
+
+impl Side {
+    /// Classify a point by its signed distance from a plain
+    pub fn of(signed_distance: f64) -> Side {
+        if tolerance::is_zero(signed_distance) {
+            Self::On
+        } else if signed_distance > 0.0 {
+            Self::Front
+        } else {
+            Self::Back
+        }
+    }
+
+    /// The side on the other part of the plain
+    pub fn opposite(self) -> Side {
+        match self {
+            Self::Front => Self::Back,
+            Self::Back => Self::Front,
+            Self::On => Self::On
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_distances() {
+        assert_eq!(Side::of(2.0), Side::Front);
+        assert_eq!(Side::of(-0.5), Side::Back);
+        assert_eq!(Side::of(0.0), Side::On);
+        assert_eq!(Side::of(1e-12), Side::On);
+    }
+
+    #[test]
+    fn opposite_sides() {
+        assert_eq!(Side::Front.opposite(), Side::Back);
+        assert_eq!(Side::Back.opposite(), Side::Front);
+        assert_eq!(Side::On.opposite(), Side::On);
+    }
+}
@@ -0,0 +1,6 @@
+//! Analytic geometry of points and lines in the plane, mirroring the geometry of space
+
+pub mod vector;
+pub mod line;
+pub mod segment;
+pub mod polygon;
@@ -0,0 +1,97 @@
+//! Lines in the plane
+
+use std::f64::consts::PI;
+
+use crate::math::tolerance;
+
+use super::vector::Vector2;
+
+pub mod relations;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line2 {
+    pub point: Vector2,
+    pub direction: Vector2
+}
+
+impl Line2 {
+
+    pub fn new(point: Vector2, direction: Vector2) -> Line2 {
+        Line2 { point, direction }
+    }
+
+    pub fn from_two_points(origin: Vector2, other_point: &Vector2) -> Self {
+        Self::new(origin, *other_point - origin)
+    }
+
+    pub fn is_on_line(&self, other_point: &Vector2) -> bool {
+        tolerance::is_zero(self.distance_from_point(other_point))
+    }
+
+    /// Compute the orthogonal projection of a point onto the line
+    pub fn projection_of(&self, other_point: &Vector2) -> Vector2 {
+        let t = (*other_point - self.point) * self.direction / (self.direction * self.direction);
+        self.point + t * self.direction
+    }
+
+    /// Calculate the distance between a given point and this line
+    pub fn distance_from_point(&self, other_point: &Vector2) -> f64 {
+        self.signed_distance_from_point(other_point).abs()
+    }
+
+    /// Calculate the signed distance of a point from this line,
+    /// positive to the left of its direction and negative to the right of it
+    pub fn signed_distance_from_point(&self, other_point: &Vector2) -> f64 {
+        Vector2::cross(&self.direction, &(*other_point - self.point)) / self.direction.length()
+    }
+
+    /// find intersection of two lines, if existing and single
+    pub fn intersection(line1: &Line2, line2: &Line2) -> Option<Vector2> {
+        if line1.direction.is_lindep(&line2.direction) {
+            return None;
+        }
+        // solve p1 + tu1 = p2 + su2 with vectoric products, so the test above is the only singular case
+        let offset = line2.point - line1.point;
+        let t = Vector2::cross(&offset, &line2.direction) / Vector2::cross(&line1.direction, &line2.direction);
+        Some(line1.point + t * line1.direction)
+    }
+
+    /// Find the angle (0 < x < PI/2) between two lines, in radians
+    pub fn angle_between(line1: &Line2, line2: &Line2) -> f64 {
+        let angle = Vector2::angle_between(&line1.direction, &line2.direction);
+        // angle between lines must be between 0 and 90 degrees
+        if angle > PI / 2.0 {
+            PI - angle
+        } else {
+            angle
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn distance_works() {
+        let line = Line2::new(Vector2(0.0, 1.0), Vector2(2.0, 0.0));
+        assert_eq!(line.distance_from_point(&Vector2(5.0, 4.0)), 3.0);
+        assert_eq!(line.signed_distance_from_point(&Vector2(5.0, -1.0)), -2.0);
+        assert_eq!(line.projection_of(&Vector2(5.0, 4.0)), Vector2(5.0, 1.0));
+        assert!(line.is_on_line(&Vector2(-7.0, 1.0)));
+    }
+
+    #[test]
+    fn intersection_works() {
+        let line1 = Line2::from_two_points(Vector2(0.0, 0.0), &Vector2(2.0, 2.0));
+        let line2 = Line2::new(Vector2(0.0, 4.0), Vector2(1.0, -1.0));
+        let intersection = Line2::intersection(&line1, &line2).unwrap();
+        assert!((intersection - Vector2(2.0, 2.0)).length() < EPSILON);
+        let parallel = Line2::new(Vector2(0.0, 1.0), Vector2(3.0, 3.0));
+        assert_eq!(Line2::intersection(&line1, &parallel), None);
+        // a vertical line has a zero x coefficient
+        let vertical = Line2::new(Vector2(3.0, 0.0), Vector2(0.0, 1.0));
+        assert!((Line2::intersection(&line1, &vertical).unwrap() - Vector2(3.0, 3.0)).length() < EPSILON);
+    }
+}
@@ -0,0 +1,64 @@
+//! A module to describe a relation between two lines in the plane
+
+use crate::math::tolerance;
+use crate::plane2d::{vector::Vector2, line::Line2};
+
+#[derive(Debug, PartialEq)]
+pub enum Line2Relations {
+    /// The two lines share the exact same points
+    Unite,
+    /// The two lines have a constant distance between them
+    Parallel(f64),
+    /// The two lines share a point and have an angle between them
+    Intersect(Vector2, f64)
+}
+
+impl Line2Relations {
+    /// find the relation between two lines
+    pub fn of(line1: &Line2, line2: &Line2) -> Line2Relations {
+        if line1.direction.is_lindep(&line2.direction) {
+            // lines either unite or parallel
+            let distance = line1.distance_from_point(&line2.point);
+            if tolerance::is_zero(distance) { Self::Unite } else { Self::Parallel(distance) }
+        } else {
+            // lines in a plane that aren't parallel always intersect
+            let intersection = Line2::intersection(line1, line2).unwrap();
+            Self::Intersect(intersection, Line2::angle_between(line1, line2))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    #[test]
+    fn same_line() {
+        let line1 = Line2::new(Vector2(0.0, 0.0), Vector2(1.0, 0.0));
+        let line2 = Line2::new(Vector2(3.0, 0.0), Vector2(-2.0, 0.0));
+        assert_eq!(Line2Relations::of(&line1, &line2), Line2Relations::Unite)
+    }
+
+    #[test]
+    fn parallel_lines() {
+        let line1 = Line2::new(Vector2(0.0, 0.0), Vector2(1.0, 0.0));
+        let line2 = Line2::new(Vector2(0.0, 1.0), Vector2(1.0, 0.0));
+        assert_eq!(Line2Relations::of(&line1, &line2), Line2Relations::Parallel(1.0))
+    }
+
+    #[test]
+    fn intersecting_lines() {
+        let line1 = Line2::new(Vector2(0.0, 0.0), Vector2(1.0, 0.0)); // the x axis
+        let line2 = Line2::new(Vector2(0.0, 0.0), Vector2(-1.0, 1.0));
+        assert_eq!(Line2Relations::of(&line1, &line2), Line2Relations::Intersect(Vector2(0.0, 0.0), PI / 4.0));
+    }
+
+    #[test]
+    fn far_apart_lines() {
+        let line1 = Line2::new(Vector2(0.0, 0.0), Vector2(1.0, 0.0)); // the x axis
+        let line2 = Line2::new(Vector2(1e5, 1e5), Vector2(0.0, 1.0));
+        assert_eq!(Line2Relations::of(&line1, &line2), Line2Relations::Intersect(Vector2(1e5, 0.0), PI / 2.0));
+    }
+}
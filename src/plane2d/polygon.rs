@@ -0,0 +1,129 @@
+//! Simple polygons in the plane
+
+use crate::math::tolerance;
+
+use super::{vector::Vector2, segment::Segment2};
+
+/// A simple polygon, given by its vertices in order around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon2 {
+    pub vertices: Vec<Vector2>
+}
+
+impl Polygon2 {
+
+    /// Generates a polygon from its vertices
+    /// # Panics:
+    /// - If there are less than three vertices
+    pub fn new(vertices: Vec<Vector2>) -> Polygon2 {
+        if vertices.len() < 3 {
+            panic!("A polygon must have at least three vertices")
+        }
+        Polygon2 { vertices }
+    }
+
+    /// The edges of the polygon, from each vertex to the next one
+    pub fn edges(&self) -> Vec<Segment2> {
+        let count = self.vertices.len();
+        (0..count).map(|i| Segment2::new(self.vertices[i], self.vertices[(i + 1) % count])).collect()
+    }
+
+    /// The signed area by the shoelace formula, positive if the vertices are counterclockwise
+    pub fn signed_area(&self) -> f64 {
+        self.edges().iter().map(|edge| Vector2::cross(&edge.start, &edge.end)).sum::<f64>() / 2.0
+    }
+
+    pub fn area(&self) -> f64 {
+        self.signed_area().abs()
+    }
+
+    /// The center of mass of the polygon's area.
+    /// Returns None if the polygon encloses no area
+    pub fn centroid(&self) -> Option<Vector2> {
+        let signed_area = self.signed_area();
+        if tolerance::is_zero(signed_area) {
+            return None;
+        }
+        let weighted = self.edges().iter().fold(Vector2(0.0, 0.0), |sum, edge| {
+            sum + Vector2::cross(&edge.start, &edge.end) * (edge.start + edge.end)
+        });
+        Some((1.0 / (6.0 * signed_area)) * weighted)
+    }
+
+    pub fn is_counterclockwise(&self) -> bool {
+        self.signed_area() > 0.0
+    }
+
+    /// The same polygon with its vertices in the opposite order
+    pub fn reversed(&self) -> Polygon2 {
+        Polygon2::new(self.vertices.iter().rev().copied().collect())
+    }
+
+    /// Check if every turn between consecutive edges is to the same side
+    pub fn is_convex(&self) -> bool {
+        let edges = self.edges();
+        let turns: Vec<f64> = (0..edges.len())
+            .map(|i| {
+                let (edge, next) = (&edges[i], &edges[(i + 1) % edges.len()]);
+                Vector2::cross(&(edge.end - edge.start), &(next.end - next.start))
+            })
+            .filter(|turn| !tolerance::is_zero(*turn))
+            .collect();
+        turns.iter().all(|turn| *turn > 0.0) || turns.iter().all(|turn| *turn < 0.0)
+    }
+
+    /// Check if a point is inside the polygon or on its boundary
+    pub fn contains_point(&self, point: &Vector2) -> bool {
+        if self.edges().iter().any(|edge| tolerance::is_zero(edge.distance_from_point(point).0)) {
+            return true;
+        }
+        // count the crossings of a ray from the point along the positive x axis
+        let crossings = self.edges().iter()
+            .filter(|edge| {
+                let (Vector2(x1, y1), Vector2(x2, y2)) = (edge.start - *point, edge.end - *point);
+                (y1 > 0.0) != (y2 > 0.0) && x1 + (x2 - x1) * (-y1) / (y2 - y1) > 0.0
+            })
+            .count();
+        crossings % 2 == 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    fn l_shape() -> Polygon2 {
+        Polygon2::new(vec![
+            Vector2(0.0, 0.0), Vector2(2.0, 0.0), Vector2(2.0, 1.0), Vector2(1.0, 1.0), Vector2(1.0, 2.0), Vector2(0.0, 2.0)
+        ])
+    }
+
+    #[test]
+    fn measures() {
+        let polygon = l_shape();
+        assert_eq!(polygon.area(), 3.0);
+        assert!(polygon.is_counterclockwise());
+        assert!(!polygon.reversed().is_counterclockwise());
+        assert_eq!(polygon.reversed().area(), 3.0);
+        assert!((polygon.centroid().unwrap() - Vector2(5.0 / 6.0, 5.0 / 6.0)).length() < EPSILON);
+        assert!(Polygon2::new(vec![Vector2(0.0, 0.0), Vector2(1.0, 0.0), Vector2(2.0, 0.0)]).centroid().is_none());
+        assert!(!polygon.is_convex());
+        assert!(Polygon2::new(vec![Vector2(0.0, 0.0), Vector2(1.0, 0.0), Vector2(2.0, 0.0), Vector2(1.0, 1.0)]).is_convex());
+    }
+
+    #[test]
+    fn contains_point() {
+        let polygon = l_shape();
+        assert!(polygon.contains_point(&Vector2(0.5, 1.5)));
+        assert!(!polygon.contains_point(&Vector2(1.5, 1.5)));
+        assert!(polygon.contains_point(&Vector2(1.0, 1.5)));
+        assert!(!polygon.contains_point(&Vector2(-1.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_few_vertices() {
+        Polygon2::new(vec![Vector2(0.0, 0.0), Vector2(1.0, 0.0)]);
+    }
+}
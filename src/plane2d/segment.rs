@@ -0,0 +1,97 @@
+//! Line segments in the plane
+
+use crate::math::tolerance;
+
+use super::{vector::Vector2, line::Line2};
+
+/// A segment between two points, where `start` is at parameter 0 and `end` at parameter 1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment2 {
+    pub start: Vector2,
+    pub end: Vector2
+}
+
+impl Segment2 {
+
+    pub fn new(start: Vector2, end: Vector2) -> Segment2 {
+        Segment2 { start, end }
+    }
+
+    /// The line through the segment, with the segment's parameterization
+    pub fn line(&self) -> Line2 {
+        Line2::from_two_points(self.start, &self.end)
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.end - self.start).length()
+    }
+
+    /// The point at a parameter along the segment
+    pub fn point_at(&self, t: f64) -> Vector2 {
+        self.start + t * (self.end - self.start)
+    }
+
+    /// Find a common point of two segments, if existing
+    pub fn intersection(segment1: &Segment2, segment2: &Segment2) -> Option<Vector2> {
+        let (u, v) = (segment1.end - segment1.start, segment2.end - segment2.start);
+        if tolerance::is_zero(u.length()) {
+            let (distance, _) = segment2.distance_from_point(&segment1.start);
+            return if tolerance::is_zero(distance) { Some(segment1.start) } else { None };
+        }
+        if u.is_lindep(&v) {
+            if !segment1.line().is_on_line(&segment2.start) {
+                return None;
+            }
+            // collinear segments overlap where the parameters of the second one along the first one meet [0, 1]
+            let parameter = |point: &Vector2| (*point - segment1.start) * u / (u * u);
+            let (a, b) = (parameter(&segment2.start), parameter(&segment2.end));
+            let (low, high) = (a.min(b).max(0.0), a.max(b).min(1.0));
+            return if low <= high + tolerance::EPSILON { Some(segment1.point_at(low)) } else { None };
+        }
+        let denominator = Vector2::cross(&u, &v);
+        // solve start1 + t u = start2 + s v with vectoric products
+        let offset = segment2.start - segment1.start;
+        let (t, s) = (Vector2::cross(&offset, &v) / denominator, Vector2::cross(&offset, &u) / denominator);
+        let within = |x: f64| (-tolerance::EPSILON..=1.0 + tolerance::EPSILON).contains(&x);
+        if within(t) && within(s) { Some(segment1.point_at(t)) } else { None }
+    }
+
+    /// Find the distance of a point from the segment, and the closest point of the segment to it
+    pub fn distance_from_point(&self, point: &Vector2) -> (f64, Vector2) {
+        let direction = self.end - self.start;
+        let squared = direction * direction;
+        let t = if squared == 0.0 { 0.0 } else { ((*point - self.start) * direction / squared).clamp(0.0, 1.0) };
+        let closest = self.point_at(t);
+        ((*point - closest).length(), closest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn intersection_works() {
+        let segment1 = Segment2::new(Vector2(0.0, 0.0), Vector2(2.0, 2.0));
+        let segment2 = Segment2::new(Vector2(0.0, 2.0), Vector2(2.0, 0.0));
+        assert!((Segment2::intersection(&segment1, &segment2).unwrap() - Vector2(1.0, 1.0)).length() < EPSILON);
+        let short = Segment2::new(Vector2(0.0, 2.0), Vector2(0.5, 1.5));
+        assert_eq!(Segment2::intersection(&segment1, &short), None);
+
+        let continuation = Segment2::new(Vector2(2.0, 2.0), Vector2(3.0, 3.0));
+        assert_eq!(Segment2::intersection(&segment1, &continuation), Some(Vector2(2.0, 2.0)));
+        let overlapping = Segment2::new(Vector2(1.0, 1.0), Vector2(3.0, 3.0));
+        assert_eq!(Segment2::intersection(&segment1, &overlapping), Some(Vector2(1.0, 1.0)));
+        let apart = Segment2::new(Vector2(3.0, 3.0), Vector2(4.0, 4.0));
+        assert_eq!(Segment2::intersection(&segment1, &apart), None);
+    }
+
+    #[test]
+    fn distance_works() {
+        let segment = Segment2::new(Vector2(0.0, 0.0), Vector2(4.0, 0.0));
+        assert_eq!(segment.distance_from_point(&Vector2(2.0, 3.0)), (3.0, Vector2(2.0, 0.0)));
+        assert_eq!(segment.distance_from_point(&Vector2(7.0, 4.0)), (5.0, Vector2(4.0, 0.0)));
+        assert_eq!(segment.length(), 4.0);
+    }
+}
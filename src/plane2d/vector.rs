@@ -0,0 +1,118 @@
+//! Vectors in the plane
+
+use std::ops::{Add, Sub, Mul, Neg};
+
+use crate::math::tolerance;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector2(pub f64, pub f64);
+
+impl Vector2 {
+
+    /// The mathematical length of a vector
+    pub fn length(&self) -> f64 {
+        ((*self) * (*self)).sqrt()
+    }
+
+    /// The z component of the vectoric product of two vectors in the xy plane,
+    /// i.e. the signed area of the parallelogram they span, positive for a counterclockwise turn
+    pub fn cross(u: &Vector2, v: &Vector2) -> f64 {
+        u.0 * v.1 - u.1 * v.0
+    }
+
+    /// Check if two vectors are linearly dependent
+    pub fn is_lindep(&self, other: &Vector2) -> bool {
+        let scale = self.length() * other.length();
+        // compare the sine of the angle rather than the area, so the test doesn't depend on the lengths
+        scale == 0.0 || tolerance::is_zero(Vector2::cross(self, other) / scale)
+    }
+
+    /// The vector rotated a quarter turn counterclockwise
+    pub fn perpendicular(&self) -> Vector2 {
+        Vector2(-self.1, self.0)
+    }
+
+    /// Compute the angle between two vectors, in radians
+    pub fn angle_between(u: &Vector2, v: &Vector2) -> f64 {
+        Vector2::cross(u, v).abs().atan2((*u) * (*v))
+    }
+
+    /// Compute the signed angle (-PI < x <= PI) rotating the first vector to the second, positive counterclockwise
+    pub fn signed_angle_between(u: &Vector2, v: &Vector2) -> f64 {
+        Vector2::cross(u, v).atan2((*u) * (*v))
+    }
+}
+
+/// (u, v) coordinates, as given by a plain basis
+impl From<(f64, f64)> for Vector2 {
+    fn from((x, y): (f64, f64)) -> Self {
+        Vector2(x, y)
+    }
+}
+
+impl Add for Vector2 {
+    type Output = Self;
+
+    /// Vectoric addition of two vectors in the plane
+    fn add(self, rhs: Self) -> Self::Output {
+        Vector2(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Vector2 {
+    type Output = Self;
+
+    /// Vectoric subtraction of two vectors in the plane
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector2(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Mul for Vector2 {
+    type Output = f64;
+
+    /// Scalaric product of two vectors
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.0 * rhs.0 + self.1 * rhs.1
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Self;
+
+    /// The opposite vector, with the same length
+    fn neg(self) -> Self::Output {
+        Vector2(-self.0, -self.1)
+    }
+}
+
+/// multiply a vector by a scalar
+impl Mul<Vector2> for f64 {
+    type Output = Vector2;
+
+    fn mul(self, rhs: Vector2) -> Self::Output {
+        Vector2(self * rhs.0, self * rhs.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    #[test]
+    fn products_and_angles() {
+        let (u, v) = (Vector2(3.0, 0.0), Vector2(1.0, 1.0));
+        assert_eq!(u.length(), 3.0);
+        assert_eq!(u * v, 3.0);
+        assert_eq!(Vector2::cross(&u, &v), 3.0);
+        assert_eq!(u.perpendicular(), Vector2(0.0, 3.0));
+        assert!((Vector2::angle_between(&u, &v) - PI / 4.0).abs() < EPSILON);
+        assert!((Vector2::signed_angle_between(&v, &u) + PI / 4.0).abs() < EPSILON);
+        assert!(u.is_lindep(&Vector2(-1.0, 0.0)));
+        assert!(!u.is_lindep(&v));
+        assert_eq!(Vector2::from((1.0, 2.0)), Vector2(1.0, 2.0));
+    }
+}
@@ -0,0 +1,242 @@
+//! Planar polygons in space
+
+use crate::{vector::Vector, segment::Segment, math::tolerance, plain::{Plain, half_space::HalfSpace}};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Polygon {
+    vertices: Vec<Vector>,
+    plain: Plain
+}
+
+/// Twice the vector area of a closed polygonal chain: perpendicular to a planar polygon,
+/// with the length of twice its area
+fn doubled_vector_area(vertices: &[Vector]) -> Vector {
+    vertices.iter().zip(vertices.iter().cycle().skip(1))
+        .fold(Vector(0.0, 0.0, 0.0), |sum, (vertex1, vertex2)| sum + Vector::vectoric_product(vertex1, vertex2))
+}
+
+/// Check if a closed polygonal chain encloses any area, relative to the square of its extent
+/// so the test doesn't depend on the scale
+fn encloses_area(vertices: &[Vector]) -> bool {
+    let extent = vertices.iter().map(|vertex| (*vertex - vertices[0]).length()).fold(0.0, f64::max);
+    extent > 0.0 && !tolerance::is_zero(doubled_vector_area(vertices).length() / (extent * extent))
+}
+
+impl Polygon {
+
+    /// Generates a polygon from its vertices, in order along the boundary
+    /// # Panics:
+    /// - If the vertices are not coplanar, or don't enclose any area
+    pub fn new(vertices: Vec<Vector>) -> Polygon {
+        if Plain::through_points(&vertices).is_none() {
+            panic!("The vertices of a polygon must be coplanar and not collinear")
+        }
+        if !encloses_area(&vertices) {
+            panic!("A polygon must enclose some area")
+        }
+        let normal = doubled_vector_area(&vertices);
+        let plain = Plain::from_plumb(&vertices[0], &((1.0 / normal.length()) * normal));
+        Polygon { vertices, plain }
+    }
+
+    /// The vertices, in order along the boundary
+    pub fn vertices(&self) -> &[Vector] {
+        &self.vertices
+    }
+
+    /// The plain containing the polygon, with its plumb oriented by the order of the vertices
+    /// using the right hand rule
+    pub fn plain(&self) -> &Plain {
+        &self.plain
+    }
+
+    /// The edges, from each vertex to the next one
+    pub fn edges(&self) -> Vec<Segment> {
+        self.vertices.iter().zip(self.vertices.iter().cycle().skip(1))
+            .map(|(vertex1, vertex2)| Segment::new(*vertex1, *vertex2))
+            .collect()
+    }
+
+    pub fn area(&self) -> f64 {
+        doubled_vector_area(&self.vertices).length() / 2.0
+    }
+
+    /// The center of mass of the area of the polygon
+    pub fn centroid(&self) -> Vector {
+        // the centers of a fan of triangles, weighted by their areas signed along the normal
+        let normal = self.plain.unit_normal();
+        let first = self.vertices[0];
+        let (weighted, total) = self.vertices.windows(2).skip(1)
+            .fold((Vector(0.0, 0.0, 0.0), 0.0), |(weighted, total), pair| {
+                let area = Vector::vectoric_product(&(pair[0] - first), &(pair[1] - first)) * normal / 2.0;
+                let center = (1.0 / 3.0) * (first + pair[0] + pair[1]);
+                (weighted + area * center, total + area)
+            });
+        (1.0 / total) * weighted
+    }
+
+    /// Check if the vertices go counterclockwise around a given direction, by the right hand rule
+    pub fn is_counterclockwise(&self, direction: &Vector) -> bool {
+        doubled_vector_area(&self.vertices) * (*direction) > 0.0
+    }
+
+    /// The same polygon, with its vertices in the opposite order
+    pub fn reversed(&self) -> Polygon {
+        let vertices = self.vertices.iter().rev().copied().collect();
+        Polygon { vertices, plain: self.plain.flipped() }
+    }
+
+    /// Check if the polygon is convex: every turn along the boundary is to the same side
+    pub fn is_convex(&self) -> bool {
+        let normal = self.plain.unit_normal();
+        let count = self.vertices.len();
+        (0..count).all(|i| {
+            let (previous, current, next) = (self.vertices[i], self.vertices[(i + 1) % count], self.vertices[(i + 2) % count]);
+            Vector::vectoric_product(&(current - previous), &(next - current)) * normal > -tolerance::EPSILON
+        })
+    }
+
+    /// Check if a point is inside the polygon or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        if !self.plain.contains_point(point) {
+            return false;
+        }
+        if self.edges().iter().any(|edge| tolerance::is_zero(edge.distance_from_point(point).0)) {
+            return true;
+        }
+        // count the crossings of a ray from the point within the plain, in coordinates along two in-plain axes
+        let basis = self.plain.basis_at(point);
+        let coordinates = |vertex: &Vector| basis.coordinates_of(vertex);
+        let crossings = self.edges().iter()
+            .filter(|edge| {
+                let ((x1, y1), (x2, y2)) = (coordinates(&edge.start), coordinates(&edge.end));
+                // the edge crosses the positive part of the first axis
+                (y1 > 0.0) != (y2 > 0.0) && x1 + (x2 - x1) * (-y1) / (y2 - y1) > 0.0
+            })
+            .count();
+        crossings % 2 == 1
+    }
+
+    /// Clip the polygon to a half-space with the Sutherland–Hodgman algorithm.
+    /// Returns None if no part of the polygon with any area remains
+    pub fn clip(&self, half_space: &HalfSpace) -> Option<Polygon> {
+        let boundary = half_space.boundary();
+        let mut vertices: Vec<Vector> = vec![];
+        let mut add_vertex = |vertex: Vector| {
+            if !vertices.last().is_some_and(|last| tolerance::is_zero((*last - vertex).length())) {
+                vertices.push(vertex);
+            }
+        };
+        for edge in self.edges() {
+            let (start_inside, end_inside) = (half_space.contains(&edge.start), half_space.contains(&edge.end));
+            if start_inside {
+                add_vertex(edge.start);
+            }
+            if start_inside != end_inside {
+                // the edge crosses the boundary strictly between its ends
+                let (distance1, distance2) = (boundary.signed_distance_from(&edge.start), boundary.signed_distance_from(&edge.end));
+                add_vertex(edge.point_at(distance1 / (distance1 - distance2)));
+            }
+        }
+        if vertices.len() > 1 && tolerance::is_zero((vertices[0] - vertices[vertices.len() - 1]).length()) {
+            vertices.pop();
+        }
+
+        let remains = vertices.len() >= 3 && encloses_area(&vertices);
+        if remains { Some(Polygon { vertices, plain: self.plain }) } else { None }
+    }
+
+    /// Split the polygon by a plain into its parts in front of the plain and behind it.
+    /// A polygon in the plain is in front of it if their plumbs point to the same side, and behind it otherwise
+    pub fn split(&self, plain: &Plain) -> (Option<Polygon>, Option<Polygon>) {
+        if self.vertices.iter().all(|vertex| plain.contains_point(vertex)) {
+            return if self.plain.unit_normal() * plain.unit_normal() > 0.0 { (Some(self.clone()), None) } else { (None, Some(self.clone())) };
+        }
+        (self.clip(&HalfSpace::in_front_of(*plain)), self.clip(&HalfSpace::behind(*plain)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// The square [0, 2] x [0, 2] at z = 1, counterclockwise around the z axis
+    fn square() -> Polygon {
+        Polygon::new(vec![Vector(0.0, 0.0, 1.0), Vector(2.0, 0.0, 1.0), Vector(2.0, 2.0, 1.0), Vector(0.0, 2.0, 1.0)])
+    }
+
+    /// An L shaped hexagon at z = 0
+    fn l_shape() -> Polygon {
+        Polygon::new(vec![
+            Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0), Vector(2.0, 1.0, 0.0),
+            Vector(1.0, 1.0, 0.0), Vector(1.0, 2.0, 0.0), Vector(0.0, 2.0, 0.0)
+        ])
+    }
+
+    fn x_plain(x: f64) -> Plain {
+        Plain::from_plumb(&Vector(x, 0.0, 0.0), &Vector(1.0, 0.0, 0.0))
+    }
+
+    #[test]
+    #[should_panic]
+    fn creates_from_non_coplanar_points() {
+        Polygon::new(vec![Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(1.0, 1.0, 0.0), Vector(0.0, 1.0, 1.0)]);
+    }
+
+    #[test]
+    fn area_and_centroid() {
+        assert_eq!(square().area(), 4.0);
+        assert_eq!(square().centroid(), Vector(1.0, 1.0, 1.0));
+        let l_shape = l_shape();
+        assert_eq!(l_shape.area(), 3.0);
+        assert!((l_shape.centroid() - Vector(5.0 / 6.0, 5.0 / 6.0, 0.0)).length() < EPSILON);
+    }
+
+    #[test]
+    fn winding_and_convexity() {
+        let z = Vector(0.0, 0.0, 1.0);
+        assert!(square().is_counterclockwise(&z));
+        assert!(!square().reversed().is_counterclockwise(&z));
+        assert_eq!(square().plain.unit_normal(), z);
+        assert!(square().is_convex());
+        assert!(square().reversed().is_convex());
+        assert!(!l_shape().is_convex());
+    }
+
+    #[test]
+    fn contains_points() {
+        let l_shape = l_shape();
+        assert!(l_shape.contains_point(&Vector(0.5, 1.5, 0.0)));
+        assert!(l_shape.contains_point(&Vector(1.5, 0.5, 0.0)));
+        assert!(l_shape.contains_point(&Vector(1.5, 1.0, 0.0)));
+        assert!(!l_shape.contains_point(&Vector(1.5, 1.5, 0.0)));
+        assert!(!l_shape.contains_point(&Vector(0.5, 0.5, 1.0)));
+    }
+
+    #[test]
+    fn clips_to_half_space() {
+        let clipped = square().clip(&HalfSpace::behind(x_plain(0.5))).unwrap();
+        assert_eq!(clipped.area(), 1.0);
+        assert_eq!(clipped.plain, square().plain);
+        assert!(square().clip(&HalfSpace::behind(x_plain(-1.0))).is_none());
+        assert_eq!(square().clip(&HalfSpace::behind(x_plain(3.0))).unwrap().vertices.len(), 4);
+        // clipping at an edge leaves nothing with area
+        assert!(square().clip(&HalfSpace::behind(x_plain(0.0))).is_none());
+    }
+
+    #[test]
+    fn splits_by_plain() {
+        let (front, back) = l_shape().split(&x_plain(1.5));
+        assert_eq!(front.unwrap().area(), 0.5);
+        assert_eq!(back.unwrap().area(), 2.5);
+
+        let (front, back) = square().split(&x_plain(5.0));
+        assert!(front.is_none());
+        assert_eq!(back.unwrap(), square());
+
+        let z_plain = Plain::from_plumb(&Vector(0.0, 0.0, 1.0), &Vector(0.0, 0.0, 1.0));
+        assert_eq!(square().split(&z_plain), (Some(square()), None));
+        assert_eq!(square().split(&z_plain.flipped()), (None, Some(square())));
+    }
+}
@@ -0,0 +1,300 @@
+//! Convex polyhedra bounded by intersections of half-spaces
+
+use crate::{vector::Vector, math::tolerance, plain::{Plain, half_space::HalfSpace, side::Side}};
+
+#[derive(Debug, Clone)]
+pub struct ConvexPolyhedron {
+    pub vertices: Vec<Vector>,
+    /// The faces as indices into the vertices, ordered counterclockwise when seen from outside
+    pub faces: Vec<Vec<usize>>,
+    /// The plains of the faces, with their plumbs pointing out of the polyhedron
+    pub face_plains: Vec<Plain>,
+    /// The edges as pairs of indices into the vertices
+    pub edges: Vec<(usize, usize)>
+}
+
+/// The region common to a set of half-spaces
+#[derive(Debug)]
+pub enum HalfSpacesIntersection {
+    /// The half-spaces have no common point, or their common region is bounded and has no interior
+    Empty,
+    /// The common region extends infinitely in some direction. It may have no interior,
+    /// e.g. when opposite half-spaces meet only at their common boundary plain
+    Unbounded,
+    /// The common region is a convex polyhedron
+    Bounded(ConvexPolyhedron)
+}
+
+impl HalfSpacesIntersection {
+    /// Compute the region common to a set of half-spaces
+    pub fn of(half_spaces: &[HalfSpace]) -> HalfSpacesIntersection {
+        let normals: Vec<Vector> = half_spaces.iter().map(|half_space| half_space.boundary().unit_normal()).collect();
+
+        // the region is invariant along directions perpendicular to all plumbs, so pinning it
+        // to a plain perpendicular to them preserves emptiness and produces vertices
+        let mut pinned = half_spaces.to_vec();
+        for direction in lineality_directions(&normals) {
+            let pin = Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &direction);
+            pinned.push(HalfSpace::behind(pin));
+            pinned.push(HalfSpace::in_front_of(pin));
+        }
+        let vertices = feasible_vertices(&pinned);
+        if vertices.is_empty() {
+            return Self::Empty;
+        }
+        if has_recession_direction(&normals) {
+            return Self::Unbounded;
+        }
+        if Plain::are_coplanar(&vertices) {
+            return Self::Empty;
+        }
+        Self::Bounded(ConvexPolyhedron::from_vertices(vertices, half_spaces))
+    }
+}
+
+/// Directions perpendicular to all of the given plumbs, spanning the space of such directions
+fn lineality_directions(normals: &[Vector]) -> Vec<Vector> {
+    let first = match normals.first() {
+        None => return vec![Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0)],
+        Some(normal) => *normal
+    };
+    let independent = normals.iter().find(|normal| !tolerance::is_zero(Vector::vectoric_product(&first, normal).length()));
+    match independent {
+        None => {
+            let perpendicular = first.any_perpendicular();
+            vec![perpendicular, Vector::vectoric_product(&first, &perpendicular)]
+        },
+        Some(second) => {
+            let direction = Vector::vectoric_product(&first, second);
+            if normals.iter().all(|normal| tolerance::is_zero(*normal * direction / direction.length())) {
+                vec![direction]
+            } else {
+                vec![]
+            }
+        }
+    }
+}
+
+/// Check if some direction leads away from all of the plains, which makes a non-empty region unbounded
+fn has_recession_direction(normals: &[Vector]) -> bool {
+    if !lineality_directions(normals).is_empty() {
+        return true;
+    }
+    // the extreme directions of the cone of such directions are perpendicular to two of the plumbs
+    let leads_away = |direction: &Vector| normals.iter().all(|normal| *normal * (*direction) < tolerance::EPSILON);
+    normals.iter().enumerate().any(|(i, normal1)| {
+        normals.iter().skip(i + 1).any(|normal2| {
+            let direction = Vector::vectoric_product(normal1, normal2);
+            if tolerance::is_zero(direction.length()) {
+                return false;
+            }
+            let direction = (1.0 / direction.length()) * direction;
+            leads_away(&direction) || leads_away(&(-direction))
+        })
+    })
+}
+
+/// The distinct common points of triples of boundaries, which are inside all half-spaces
+fn feasible_vertices(half_spaces: &[HalfSpace]) -> Vec<Vector> {
+    let mut vertices: Vec<Vector> = vec![];
+    for (i, half_space1) in half_spaces.iter().enumerate() {
+        for (j, half_space2) in half_spaces.iter().enumerate().skip(i + 1) {
+            for half_space3 in half_spaces.iter().skip(j + 1) {
+                let point = Plain::intersection_point_of(half_space1.boundary(), half_space2.boundary(), half_space3.boundary());
+                if let Some(point) = point {
+                    let is_new = !vertices.iter().any(|vertex| tolerance::is_zero((*vertex - point).length()));
+                    if is_new && half_spaces.iter().all(|half_space| half_space.contains(&point)) {
+                        vertices.push(point);
+                    }
+                }
+            }
+        }
+    }
+    vertices
+}
+
+impl ConvexPolyhedron {
+
+    /// Assemble the faces and edges of the polyhedron with the given vertices, bounded by the given half-spaces
+    fn from_vertices(vertices: Vec<Vector>, half_spaces: &[HalfSpace]) -> ConvexPolyhedron {
+        let mut faces: Vec<Vec<usize>> = vec![];
+        let mut face_plains: Vec<Plain> = vec![];
+        for half_space in half_spaces {
+            let plain = half_space.boundary();
+            let mut face: Vec<usize> = (0..vertices.len())
+                .filter(|i| plain.side_of(&vertices[*i]) == Side::On)
+                .collect();
+            let face_points: Vec<Vector> = face.iter().map(|i| vertices[*i]).collect();
+            if face.len() < 3 || Plain::through_points(&face_points).is_none() {
+                continue; // the boundary only touches the polyhedron, or is redundant
+            }
+            face.sort();
+            if faces.contains(&face) {
+                continue; // another half-space with the same boundary
+            }
+            faces.push(face);
+            face_plains.push(*plain);
+        }
+
+        for (face, plain) in faces.iter_mut().zip(face_plains.iter()) {
+            order_counterclockwise(face, &vertices, &plain.unit_normal());
+        }
+
+        let mut edges: Vec<(usize, usize)> = vec![];
+        for face in &faces {
+            for (i, vertex1) in face.iter().enumerate() {
+                let vertex2 = face[(i + 1) % face.len()];
+                let edge = ((*vertex1).min(vertex2), (*vertex1).max(vertex2));
+                if !edges.contains(&edge) {
+                    edges.push(edge);
+                }
+            }
+        }
+
+        ConvexPolyhedron { vertices, faces, face_plains, edges }
+    }
+
+    /// The vertices of a face, in order counterclockwise when seen from outside
+    pub fn face_polygon(&self, face: usize) -> Vec<Vector> {
+        self.faces[face].iter().map(|i| self.vertices[*i]).collect()
+    }
+
+    /// The average of the vertices, which is inside a convex polyhedron
+    fn inner_point(&self) -> Vector {
+        (1.0 / self.vertices.len() as f64) * self.vertices.iter().fold(Vector(0.0, 0.0, 0.0), |sum, vertex| sum + *vertex)
+    }
+
+    /// The area of a face
+    pub fn face_area(&self, face: usize) -> f64 {
+        let polygon = self.face_polygon(face);
+        (1..polygon.len() - 1)
+            .map(|i| Vector::triangle_area_of_points(&polygon[0], &polygon[i], &polygon[i + 1]))
+            .sum()
+    }
+
+    pub fn surface_area(&self) -> f64 {
+        (0..self.faces.len()).map(|face| self.face_area(face)).sum()
+    }
+
+    pub fn volume(&self) -> f64 {
+        // the sum of the pyramids over each face with a common inner apex
+        let apex = self.inner_point();
+        (0..self.faces.len())
+            .map(|face| self.face_area(face) * self.face_plains[face].distance_from(&apex) / 3.0)
+            .sum()
+    }
+
+    /// Check if a point is inside the polyhedron or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        self.face_plains.iter().all(|plain| plain.side_of(point) != Side::Front)
+    }
+}
+
+/// Sort the vertices of a convex face counterclockwise around a given normal
+fn order_counterclockwise(face: &mut [usize], vertices: &[Vector], normal: &Vector) {
+    let center = (1.0 / face.len() as f64) * face.iter().fold(Vector(0.0, 0.0, 0.0), |sum, i| sum + vertices[*i]);
+    let basis = Plain::from_plumb(&center, normal).basis_at(&center);
+    let angle = |i: &usize| { let (u, v) = basis.coordinates_of(&vertices[*i]); v.atan2(u) };
+    face.sort_by(|i, j| angle(i).total_cmp(&angle(j)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// The half-spaces bounding the box [0, size]^3
+    fn cube_half_spaces(size: f64) -> Vec<HalfSpace> {
+        let (origin, corner) = (Vector(0.0, 0.0, 0.0), Vector(size, size, size));
+        let (x, y, z) = (Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        let center = 0.5 * corner;
+        [(origin, y, z), (origin, z, x), (origin, x, y), (corner, y, z), (corner, z, x), (corner, x, y)].iter()
+            .map(|(point, dir1, dir2)| HalfSpace::containing(Plain::new(point, dir1, dir2), &center))
+            .collect()
+    }
+
+    fn bounded(half_spaces: &[HalfSpace]) -> ConvexPolyhedron {
+        match HalfSpacesIntersection::of(half_spaces) {
+            HalfSpacesIntersection::Bounded(polyhedron) => polyhedron,
+            other => panic!("Expected a bounded polyhedron, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn cube() {
+        let cube = bounded(&cube_half_spaces(2.0));
+        assert_eq!(cube.vertices.len(), 8);
+        assert_eq!(cube.edges.len(), 12);
+        assert_eq!(cube.faces.len(), 6);
+        assert!(cube.faces.iter().all(|face| face.len() == 4));
+        assert!((cube.volume() - 8.0).abs() < EPSILON);
+        assert!((cube.surface_area() - 24.0).abs() < EPSILON);
+        assert!(cube.contains_point(&Vector(1.0, 1.0, 2.0)));
+        assert!(!cube.contains_point(&Vector(1.0, 1.0, 2.5)));
+    }
+
+    #[test]
+    fn faces_are_counterclockwise_from_outside() {
+        let cube = bounded(&cube_half_spaces(1.0));
+        for (i, plain) in cube.face_plains.iter().enumerate() {
+            let face = cube.face_polygon(i);
+            let normal = Vector::vectoric_product(&(face[1] - face[0]), &(face[2] - face[1]));
+            assert!(normal * plain.unit_normal() > 0.0);
+        }
+    }
+
+    #[test]
+    fn cut_corner() {
+        // the cube with a corner cut off by x + y + z <= 2.5
+        let mut half_spaces = cube_half_spaces(1.0);
+        let cut = Plain::from_three_points(&Vector(2.5, 0.0, 0.0), &Vector(0.0, 2.5, 0.0), &Vector(0.0, 0.0, 2.5));
+        half_spaces.push(HalfSpace::containing(cut, &Vector(0.0, 0.0, 0.0)));
+        let polyhedron = bounded(&half_spaces);
+        assert_eq!(polyhedron.vertices.len(), 10);
+        assert_eq!(polyhedron.faces.len(), 7);
+        assert_eq!(polyhedron.edges.len(), 15);
+        assert!((polyhedron.volume() - (1.0 - 0.125 / 6.0)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn redundant_half_spaces() {
+        let mut half_spaces = cube_half_spaces(1.0);
+        half_spaces.extend(cube_half_spaces(1.0));
+        let far = Plain::new(&Vector(0.0, 0.0, 5.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        half_spaces.push(HalfSpace::containing(far, &Vector(0.0, 0.0, 0.0)));
+        let cube = bounded(&half_spaces);
+        assert_eq!((cube.vertices.len(), cube.faces.len(), cube.edges.len()), (8, 6, 12));
+    }
+
+    #[test]
+    fn empty() {
+        let mut half_spaces = cube_half_spaces(1.0);
+        let beyond = Plain::new(&Vector(0.0, 0.0, 5.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        half_spaces.push(HalfSpace::containing(beyond, &Vector(0.0, 0.0, 6.0)));
+        assert!(matches!(HalfSpacesIntersection::of(&half_spaces), HalfSpacesIntersection::Empty));
+
+        // two opposite half-spaces of a slab that don't overlap
+        let below = Plain::new(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        let above = Plain::new(&Vector(0.0, 0.0, 1.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        let apart = [HalfSpace::containing(below, &Vector(0.0, 0.0, -1.0)), HalfSpace::containing(above, &Vector(0.0, 0.0, 2.0))];
+        assert!(matches!(HalfSpacesIntersection::of(&apart), HalfSpacesIntersection::Empty));
+    }
+
+    #[test]
+    fn unbounded() {
+        let mut half_spaces = cube_half_spaces(1.0);
+        half_spaces.pop();
+        assert!(matches!(HalfSpacesIntersection::of(&half_spaces), HalfSpacesIntersection::Unbounded));
+
+        let below = Plain::new(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        let above = Plain::new(&Vector(0.0, 0.0, 1.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0));
+        let slab = [HalfSpace::in_front_of(below), HalfSpace::behind(above)];
+        assert!(matches!(HalfSpacesIntersection::of(&slab), HalfSpacesIntersection::Unbounded));
+        assert!(matches!(HalfSpacesIntersection::of(&[]), HalfSpacesIntersection::Unbounded));
+
+        // opposite half-spaces sharing their boundary meet at a whole plain
+        let x_plain = Plain::new(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), &Vector(0.0, 0.0, 1.0));
+        let flat = [HalfSpace::in_front_of(x_plain), HalfSpace::behind(x_plain)];
+        assert!(matches!(HalfSpacesIntersection::of(&flat), HalfSpacesIntersection::Unbounded));
+    }
+}
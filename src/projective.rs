@@ -0,0 +1,454 @@
+//! Projective geometry in homogeneous coordinates, where parallel lines and plains meet at infinity
+
+use std::ops::Mul;
+
+use crate::{vector::Vector, line::Line, plain::Plain, affine::Affine3, math::tolerance};
+
+/// A point `(x, y, z, w)` in homogeneous coordinates, standing for the point `(x, y, z) / w`.
+/// Points with a zero weight are ideal: the points at infinity in the direction of their vector
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HomogeneousPoint {
+    pub vector: Vector,
+    pub weight: f64
+}
+
+/// A plain `(a, b, c, d)` in homogeneous coordinates, containing the points with `a x + b y + c z + d w = 0`.
+/// The plain with a zero normal is the plain at infinity, containing all ideal points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HomogeneousPlain {
+    pub normal: Vector,
+    pub constant: f64
+}
+
+/// A line in Plücker coordinates: a direction and the moment `p x direction` of any point p on the line.
+/// Lines with a zero direction are at infinity, where parallel plains meet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HomogeneousLine {
+    pub direction: Vector,
+    pub moment: Vector
+}
+
+/// Check if a homogeneous vector vanishes relative to the scale of its coordinates
+fn is_negligible(part: f64, scale: f64) -> bool {
+    scale == 0.0 || tolerance::is_zero(part / scale)
+}
+
+impl HomogeneousPoint {
+
+    pub fn new(vector: Vector, weight: f64) -> HomogeneousPoint {
+        HomogeneousPoint { vector, weight }
+    }
+
+    /// The homogeneous coordinates of a point in space
+    pub fn from_point(point: &Vector) -> HomogeneousPoint {
+        HomogeneousPoint::new(*point, 1.0)
+    }
+
+    /// The point at infinity in a given direction, shared by all lines parallel to it
+    pub fn ideal(direction: &Vector) -> HomogeneousPoint {
+        HomogeneousPoint::new(*direction, 0.0)
+    }
+
+    pub fn is_ideal(&self) -> bool {
+        is_negligible(self.weight, self.vector.length().max(self.weight.abs()))
+    }
+
+    /// The point in space, or None for an ideal point
+    pub fn to_point(&self) -> Option<Vector> {
+        if self.is_ideal() { None } else { Some((1.0 / self.weight) * self.vector) }
+    }
+
+    /// Check if two homogeneous points are the same point, i.e. proportional
+    pub fn coincides(&self, other: &HomogeneousPoint) -> bool {
+        // all 2x2 minors of the two coordinate rows vanish
+        let cross = Vector::vectoric_product(&self.vector, &other.vector);
+        let mixed = self.weight * other.vector - other.weight * self.vector;
+        let scale = (self.vector.length() + self.weight.abs()) * (other.vector.length() + other.weight.abs());
+        is_negligible(cross.length() + mixed.length(), scale)
+    }
+}
+
+impl HomogeneousPlain {
+
+    pub fn new(normal: Vector, constant: f64) -> HomogeneousPlain {
+        HomogeneousPlain { normal, constant }
+    }
+
+    /// The homogeneous coordinates of a plain in space, keeping the direction of its plumb
+    pub fn from_plain(plain: &Plain) -> HomogeneousPlain {
+        let normal = plain.unit_normal();
+        HomogeneousPlain::new(normal, -(normal * plain.projection_of(&Vector(0.0, 0.0, 0.0))))
+    }
+
+    /// The plain containing all points at infinity
+    pub fn at_infinity() -> HomogeneousPlain {
+        HomogeneousPlain::new(Vector(0.0, 0.0, 0.0), 1.0)
+    }
+
+    pub fn is_at_infinity(&self) -> bool {
+        is_negligible(self.normal.length(), self.normal.length().max(self.constant.abs()))
+    }
+
+    /// The plain in space, or None for the plain at infinity
+    pub fn to_plain(&self) -> Option<Plain> {
+        if self.is_at_infinity() {
+            return None;
+        }
+        let point = (-self.constant / (self.normal * self.normal)) * self.normal;
+        Some(Plain::from_plumb(&point, &self.normal))
+    }
+
+    /// The value of the plain's equation at a point, zero for points on the plain
+    pub fn compute(&self, point: &HomogeneousPoint) -> f64 {
+        self.normal * point.vector + self.constant * point.weight
+    }
+
+    pub fn contains_point(&self, point: &HomogeneousPoint) -> bool {
+        let scale = (self.normal.length() + self.constant.abs()) * (point.vector.length() + point.weight.abs());
+        is_negligible(self.compute(point), scale)
+    }
+
+    /// The plain through three points. It vanishes if the points are collinear
+    pub fn join(point1: &HomogeneousPoint, point2: &HomogeneousPoint, point3: &HomogeneousPoint) -> HomogeneousPlain {
+        HomogeneousLine::join(point1, point2).join_point(point3)
+    }
+
+    /// The common point of three plains, ideal if they are all parallel to a single line.
+    /// It vanishes if the plains share a line
+    pub fn meet(plain1: &HomogeneousPlain, plain2: &HomogeneousPlain, plain3: &HomogeneousPlain) -> HomogeneousPoint {
+        // Cramer's rule, without dividing by the determinant
+        let (n1, n2, n3) = (plain1.normal, plain2.normal, plain3.normal);
+        let vector = -plain1.constant * Vector::vectoric_product(&n2, &n3)
+            - plain2.constant * Vector::vectoric_product(&n3, &n1)
+            - plain3.constant * Vector::vectoric_product(&n1, &n2);
+        HomogeneousPoint::new(vector, Vector::triple_product(&n1, &n2, &n3))
+    }
+}
+
+impl HomogeneousLine {
+
+    pub fn new(direction: Vector, moment: Vector) -> HomogeneousLine {
+        HomogeneousLine { direction, moment }
+    }
+
+    /// The Plücker coordinates of a line in space
+    pub fn from_line(line: &Line) -> HomogeneousLine {
+        HomogeneousLine::new(line.direction, Vector::vectoric_product(&line.point, &line.direction))
+    }
+
+    pub fn is_at_infinity(&self) -> bool {
+        is_negligible(self.direction.length(), self.direction.length().max(self.moment.length()))
+    }
+
+    /// The line in space, through its point closest to the origin, or None for a line at infinity
+    pub fn to_line(&self) -> Option<Line> {
+        if self.is_at_infinity() {
+            return None;
+        }
+        let direction = self.direction;
+        let point = (1.0 / (direction * direction)) * Vector::vectoric_product(&direction, &self.moment);
+        Some(Line::new(point, direction))
+    }
+
+    /// The line through two points, directed from the first to the second. It vanishes if the points coincide
+    pub fn join(point1: &HomogeneousPoint, point2: &HomogeneousPoint) -> HomogeneousLine {
+        HomogeneousLine::new(
+            point1.weight * point2.vector - point2.weight * point1.vector,
+            Vector::vectoric_product(&point1.vector, &point2.vector)
+        )
+    }
+
+    /// The line two plains share, at infinity if they are parallel. It vanishes if the plains coincide
+    pub fn meet(plain1: &HomogeneousPlain, plain2: &HomogeneousPlain) -> HomogeneousLine {
+        HomogeneousLine::new(
+            Vector::vectoric_product(&plain1.normal, &plain2.normal),
+            plain1.constant * plain2.normal - plain2.constant * plain1.normal
+        )
+    }
+
+    /// The plain through the line and a point. It vanishes if the point is on the line
+    pub fn join_point(&self, point: &HomogeneousPoint) -> HomogeneousPlain {
+        HomogeneousPlain::new(
+            Vector::vectoric_product(&self.direction, &point.vector) + point.weight * self.moment,
+            -(self.moment * point.vector)
+        )
+    }
+
+    /// The point where the line meets a plain, ideal if they are parallel. It vanishes if the plain contains the line
+    pub fn meet_plain(&self, plain: &HomogeneousPlain) -> HomogeneousPoint {
+        HomogeneousPoint::new(
+            Vector::vectoric_product(&plain.normal, &self.moment) - plain.constant * self.direction,
+            plain.normal * self.direction
+        )
+    }
+
+    pub fn contains_point(&self, point: &HomogeneousPoint) -> bool {
+        // the plain through the line and the point vanishes; the second condition matters for lines at infinity
+        let offset = Vector::vectoric_product(&point.vector, &self.direction) - point.weight * self.moment;
+        let scale = (self.direction.length() + self.moment.length()) * (point.vector.length() + point.weight.abs());
+        is_negligible(offset.length() + (self.moment * point.vector).abs(), scale)
+    }
+
+    /// Check if two lines lie in a common plain, i.e. they meet, possibly at infinity
+    pub fn are_coplanar(line1: &HomogeneousLine, line2: &HomogeneousLine) -> bool {
+        let reciprocal = line1.direction * line2.moment + line2.direction * line1.moment;
+        let scale = (line1.direction.length() + line1.moment.length()) * (line2.direction.length() + line2.moment.length());
+        is_negligible(reciprocal, scale)
+    }
+
+    /// The point where two coplanar lines meet, ideal if they are parallel.
+    /// For skew lines, this is the point of the first line closest to the second
+    pub fn meet_line(&self, other: &HomogeneousLine) -> HomogeneousPoint {
+        // unlike a line and a plain, two lines meet through an auxiliary plain containing one of them.
+        // the plain along the common perpendicular vanishes for parallel lines, so they are the one special case
+        let perpendicular = Vector::vectoric_product(&self.direction, &other.direction);
+        if is_negligible(perpendicular.length(), self.direction.length() * other.direction.length()) {
+            // parallel lines have no common perpendicular, and meet in the direction they share
+            return HomogeneousPoint::ideal(&self.direction);
+        }
+        // meet the first line with the plain through the second one, parallel to the common perpendicular
+        self.meet_plain(&other.join_point(&HomogeneousPoint::ideal(&perpendicular)))
+    }
+}
+
+/// A projective transformation, a 4x4 matrix acting on homogeneous coordinates, stored as its rows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectiveTransform(pub [[f64; 4]; 4]);
+
+impl ProjectiveTransform {
+
+    pub fn identity() -> ProjectiveTransform {
+        ProjectiveTransform(std::array::from_fn(|i| std::array::from_fn(|j| if i == j { 1.0 } else { 0.0 })))
+    }
+
+    /// The projective transformation of an affine transformation, which keeps the plain at infinity in place
+    pub fn from_affine(affine: &Affine3) -> ProjectiveTransform {
+        let rows = affine.linear.rows();
+        let translation = [affine.translation.0, affine.translation.1, affine.translation.2];
+        ProjectiveTransform(std::array::from_fn(|i| if i < 3 {
+            [rows[i].0, rows[i].1, rows[i].2, translation[i]]
+        } else {
+            [0.0, 0.0, 0.0, 1.0]
+        }))
+    }
+
+    /// The central projection from the origin onto the plain z = distance, looking along the positive z axis.
+    /// Points on the plain z = 0 are sent to infinity
+    /// # Panics:
+    /// - If the distance is zero
+    pub fn perspective(distance: f64) -> ProjectiveTransform {
+        if distance == 0.0 {
+            panic!("The projection plain can't pass through the center of projection")
+        }
+        ProjectiveTransform([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0 / distance, 0.0]
+        ])
+    }
+
+    pub fn transpose(&self) -> ProjectiveTransform {
+        ProjectiveTransform(std::array::from_fn(|i| std::array::from_fn(|j| self.0[j][i])))
+    }
+
+    /// The inverse transformation, by Gauss-Jordan elimination with partial pivoting.
+    /// Fails if the matrix is singular
+    pub fn inverse(&self) -> Result<ProjectiveTransform, ()> {
+        let scale = self.0.iter().flatten().map(|entry| entry.abs()).fold(0.0, f64::max);
+        let mut matrix = self.0;
+        let mut inverse = ProjectiveTransform::identity().0;
+        for column in 0..4 {
+            let pivot = (column..4).max_by(|i, j| matrix[*i][column].abs().total_cmp(&matrix[*j][column].abs())).unwrap();
+            if is_negligible(matrix[pivot][column], scale) {
+                return Err(());
+            }
+            matrix.swap(column, pivot);
+            inverse.swap(column, pivot);
+            let factor = 1.0 / matrix[column][column];
+            matrix[column] = matrix[column].map(|entry| factor * entry);
+            inverse[column] = inverse[column].map(|entry| factor * entry);
+            for row in (0..4).filter(|row| *row != column) {
+                let multiple = matrix[row][column];
+                matrix[row] = std::array::from_fn(|j| matrix[row][j] - multiple * matrix[column][j]);
+                inverse[row] = std::array::from_fn(|j| inverse[row][j] - multiple * inverse[column][j]);
+            }
+        }
+        Ok(ProjectiveTransform(inverse))
+    }
+
+    fn apply(&self, coordinates: [f64; 4]) -> [f64; 4] {
+        self.0.map(|row| (0..4).map(|j| row[j] * coordinates[j]).sum())
+    }
+
+    pub fn apply_to_point(&self, point: &HomogeneousPoint) -> HomogeneousPoint {
+        let Vector(x, y, z) = point.vector;
+        let [x, y, z, w] = self.apply([x, y, z, point.weight]);
+        HomogeneousPoint::new(Vector(x, y, z), w)
+    }
+
+    /// Apply the transformation to a plain, by the inverse-transpose.
+    /// Fails if the transformation is singular
+    pub fn apply_to_plain(&self, plain: &HomogeneousPlain) -> Result<HomogeneousPlain, ()> {
+        let Vector(a, b, c) = plain.normal;
+        let [a, b, c, d] = self.inverse()?.transpose().apply([a, b, c, plain.constant]);
+        Ok(HomogeneousPlain::new(Vector(a, b, c), d))
+    }
+
+    /// Apply the transformation to a line, including lines at infinity.
+    /// The line is the antisymmetric matrix `L = X Y^T - Y X^T` of any two of its points, which maps to `T L T^T`
+    pub fn apply_to_line(&self, line: &HomogeneousLine) -> HomogeneousLine {
+        let (Vector(d0, d1, d2), Vector(m0, m1, m2)) = (line.direction, line.moment);
+        let matrix = ProjectiveTransform([
+            [0.0, m2, -m1, -d0],
+            [-m2, 0.0, m0, -d1],
+            [m1, -m0, 0.0, -d2],
+            [d0, d1, d2, 0.0]
+        ]);
+        let ProjectiveTransform(image) = *self * matrix * self.transpose();
+        HomogeneousLine::new(
+            Vector(-image[0][3], -image[1][3], -image[2][3]),
+            Vector(image[1][2], image[2][0], image[0][1])
+        )
+    }
+}
+
+impl Mul for ProjectiveTransform {
+    type Output = Self;
+
+    /// Compose two transformations, applying the right one first
+    fn mul(self, rhs: Self) -> Self::Output {
+        ProjectiveTransform(std::array::from_fn(|i| std::array::from_fn(|j| (0..4).map(|k| self.0[i][k] * rhs.0[k][j]).sum())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close};
+
+    fn point(x: f64, y: f64, z: f64) -> HomogeneousPoint {
+        HomogeneousPoint::from_point(&Vector(x, y, z))
+    }
+
+    #[test]
+    fn converts_points_plains_and_lines() {
+        let p = HomogeneousPoint::new(Vector(2.0, 4.0, 6.0), 2.0);
+        assert_close(&p.to_point().unwrap(), &Vector(1.0, 2.0, 3.0));
+        assert!(p.coincides(&point(1.0, 2.0, 3.0)));
+        assert!(HomogeneousPoint::ideal(&Vector(1.0, 0.0, 0.0)).to_point().is_none());
+
+        let plain = Plain::from_plumb(&Vector(0.0, 0.0, 2.0), &Vector(0.0, 0.0, 1.0));
+        let homogeneous = HomogeneousPlain::from_plain(&plain);
+        assert!(homogeneous.contains_point(&point(5.0, -1.0, 2.0)));
+        assert!(homogeneous.to_plain().unwrap().contains_point(&Vector(3.0, 3.0, 2.0)));
+        assert!(HomogeneousPlain::at_infinity().contains_point(&HomogeneousPoint::ideal(&Vector(1.0, 2.0, 3.0))));
+        assert!(HomogeneousPlain::at_infinity().to_plain().is_none());
+
+        let line = Line::new(Vector(1.0, 1.0, 0.0), Vector(0.0, 0.0, 2.0));
+        let homogeneous = HomogeneousLine::from_line(&line);
+        assert!(homogeneous.contains_point(&point(1.0, 1.0, 7.0)));
+        assert!(homogeneous.contains_point(&HomogeneousPoint::ideal(&Vector(0.0, 0.0, -1.0))));
+        let back = homogeneous.to_line().unwrap();
+        assert!(back.is_on_line(&Vector(1.0, 1.0, 0.0)) && back.is_on_line(&Vector(1.0, 1.0, 5.0)));
+    }
+
+    #[test]
+    fn joins_and_meets() {
+        let (a, b, c) = (point(1.0, 0.0, 0.0), point(0.0, 1.0, 0.0), point(0.0, 0.0, 1.0));
+        let plain = HomogeneousPlain::join(&a, &b, &c);
+        assert!(plain.contains_point(&a) && plain.contains_point(&b) && plain.contains_point(&c));
+        assert!(!plain.contains_point(&point(0.0, 0.0, 0.0)));
+
+        let line = HomogeneousLine::join(&a, &b);
+        let z = HomogeneousLine::join(&point(0.0, 0.0, 0.0), &c);
+        assert!(line.meet_plain(&HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.5, 0.0, 0.0), &Vector(1.0, 0.0, 0.0))))
+            .coincides(&point(0.5, 0.5, 0.0)));
+        assert!(z.meet_plain(&plain).coincides(&c));
+
+        let x = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(1.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0)));
+        let y = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, 2.0, 0.0), &Vector(0.0, 1.0, 0.0)));
+        let z_plain = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, 0.0, 3.0), &Vector(0.0, 0.0, 1.0)));
+        assert!(HomogeneousPlain::meet(&x, &y, &z_plain).coincides(&point(1.0, 2.0, 3.0)));
+        let meet = HomogeneousLine::meet(&x, &y);
+        assert!(meet.contains_point(&point(1.0, 2.0, -4.0)));
+    }
+
+    #[test]
+    fn parallels_meet_at_infinity() {
+        let line1 = HomogeneousLine::from_line(&Line::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 0.0)));
+        let line2 = HomogeneousLine::from_line(&Line::new(Vector(0.0, 3.0, 0.0), Vector(-2.0, -2.0, 0.0)));
+        assert!(HomogeneousLine::are_coplanar(&line1, &line2));
+        let meet = line1.meet_line(&line2);
+        assert!(meet.is_ideal());
+        assert!(meet.coincides(&HomogeneousPoint::ideal(&Vector(1.0, 1.0, 0.0))));
+
+        let crossing = HomogeneousLine::from_line(&Line::new(Vector(0.0, 4.0, 0.0), Vector(1.0, -1.0, 0.0)));
+        assert!(line1.meet_line(&crossing).coincides(&point(2.0, 2.0, 0.0)));
+        let skew = HomogeneousLine::from_line(&Line::new(Vector(0.0, 4.0, 1.0), Vector(1.0, -1.0, 0.0)));
+        assert!(!HomogeneousLine::are_coplanar(&line1, &skew));
+        assert!(line1.meet_line(&skew).coincides(&point(2.0, 2.0, 0.0)));
+
+        // parallel plains meet at a line at infinity, in the ideal points of both
+        let plain1 = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &Vector(0.0, 0.0, 1.0)));
+        let plain2 = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, 0.0, 5.0), &Vector(0.0, 0.0, 1.0)));
+        let line = HomogeneousLine::meet(&plain1, &plain2);
+        assert!(line.is_at_infinity() && line.to_line().is_none());
+        assert!(line.contains_point(&HomogeneousPoint::ideal(&Vector(1.0, 2.0, 0.0))));
+
+        let parallel = HomogeneousLine::from_line(&Line::new(Vector(0.0, 0.0, 5.0), Vector(1.0, 0.0, 0.0)));
+        assert!(parallel.meet_plain(&plain1).coincides(&HomogeneousPoint::ideal(&Vector(1.0, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn transforms() {
+        let affine = Affine3::translation(&Vector(1.0, 0.0, 0.0)) * Affine3::rotation(&Vector(0.0, 0.0, 1.0), 0.5);
+        let transform = ProjectiveTransform::from_affine(&affine);
+        let p = Vector(1.0, 2.0, 3.0);
+        assert_close(&transform.apply_to_point(&HomogeneousPoint::from_point(&p)).to_point().unwrap(), &affine.apply_to_point(&p));
+        let inverse = transform.inverse().unwrap();
+        assert_eq!((inverse * transform).apply_to_point(&point(1.0, 2.0, 3.0)).to_point().map(|q| (q - p).length() < EPSILON), Some(true));
+
+        // a perspective projection sends parallel lines to lines meeting at a vanishing point
+        let perspective = ProjectiveTransform::perspective(1.0);
+        let rails = [Line::new(Vector(-1.0, -1.0, 1.0), Vector(0.0, 0.0, 1.0)), Line::new(Vector(1.0, -1.0, 1.0), Vector(0.0, 0.0, 1.0))];
+        let images: Vec<HomogeneousLine> = rails.iter().map(|rail| perspective.apply_to_line(&HomogeneousLine::from_line(rail))).collect();
+        let vanishing = images[0].meet_line(&images[1]);
+        assert!(vanishing.coincides(&point(0.0, 0.0, 1.0)));
+        assert!(perspective.apply_to_point(&point(1.0, 1.0, 0.0)).is_ideal());
+
+        let plain = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, -1.0, 0.0), &Vector(0.0, 1.0, 1.0)));
+        let image = transform.apply_to_plain(&plain).unwrap();
+        let on_plain = point(3.0, -1.0, 0.0);
+        assert!(image.contains_point(&transform.apply_to_point(&on_plain)));
+        assert_eq!(perspective.apply_to_plain(&plain), Err(()));
+
+        let line = Line::new(Vector(1.0, 2.0, 3.0), Vector(1.0, -1.0, 2.0));
+        let image = transform.apply_to_line(&HomogeneousLine::from_line(&line)).to_line().unwrap();
+        assert!(image.is_on_line(&affine.apply_to_point(&line.point)));
+        assert!(image.is_on_line(&affine.apply_to_point(&(line.point + line.direction))));
+    }
+
+    #[test]
+    fn transforms_lines_at_infinity() {
+        // the plains x = 0 and x = 2 meet at a line at infinity, which the perspective brings into view
+        let plain1 = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0)));
+        let plain2 = HomogeneousPlain::from_plain(&Plain::from_plumb(&Vector(2.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0)));
+        let horizon = HomogeneousLine::meet(&plain1, &plain2);
+        assert!(horizon.is_at_infinity());
+        let image = ProjectiveTransform::perspective(1.0).apply_to_line(&horizon);
+        assert!(!image.is_at_infinity());
+        assert!(image.contains_point(&point(0.0, 3.0, 1.0)) && image.contains_point(&point(0.0, -2.0, 1.0)));
+        let vanishing_line = image.to_line().unwrap();
+        assert!(vanishing_line.is_on_line(&Vector(0.0, 5.0, 1.0)));
+
+        // an affine transformation keeps it at infinity, as the ideal line of the transformed plains
+        let affine = Affine3::rotation(&Vector(0.0, 0.0, 1.0), PI / 2.0);
+        let image = ProjectiveTransform::from_affine(&affine).apply_to_line(&horizon);
+        assert!(image.is_at_infinity() && image.moment.length() > EPSILON);
+        assert!(image.contains_point(&HomogeneousPoint::ideal(&Vector(1.0, 0.0, 0.0))));
+        assert!(!image.contains_point(&HomogeneousPoint::ideal(&Vector(0.0, 1.0, 0.0))));
+    }
+}
@@ -0,0 +1,178 @@
+//! A module for pyramids with an apex over a planar polygonal base
+
+use crate::{vector::Vector, line::Line, polygon::Polygon, math::tolerance, plain::{Plain, half_space::HalfSpace}};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pyramid {
+    apex: Vector,
+    base: Polygon
+}
+
+impl Pyramid {
+
+    /// Generates a pyramid from an apex and the ordered vertices of a planar base polygon, which may be non-convex
+    /// # Panics:
+    /// - If the base vertices are not coplanar, are collinear, or don't enclose any area
+    /// - If the apex lies in the plain of the base
+    pub fn new(apex: Vector, base: Vec<Vector>) -> Pyramid {
+        let base = Polygon::new(base);
+        if base.plain().contains_point(&apex) {
+            panic!("The apex of a pyramid cannot lie in the plain of its base")
+        }
+        Pyramid { apex, base }
+    }
+
+    pub fn apex(&self) -> &Vector {
+        &self.apex
+    }
+
+    /// The base polygon, with its plain oriented by the order of its vertices
+    pub fn base(&self) -> &Polygon {
+        &self.base
+    }
+
+    fn base_half_space(&self) -> HalfSpace {
+        HalfSpace::containing(*self.base.plain(), &self.apex)
+    }
+
+    fn lateral_half_space(&self, edge: usize) -> HalfSpace {
+        let vertices = self.base.vertices();
+        let (vertex1, vertex2) = (vertices[edge], vertices[(edge + 1) % vertices.len()]);
+        // the base lies to the left of each edge, looking against the plumb of the winding,
+        // so a lateral face's plumb points out of the pyramid when it points away from that side
+        let along = vertex2 - vertex1;
+        let inward = Vector::vectoric_product(&self.base.plain().unit_normal(), &along);
+        let plumb = Vector::vectoric_product(&along, &(self.apex - vertex1));
+        let plumb = if plumb * inward > 0.0 { -plumb } else { plumb };
+        HalfSpace::behind(Plain::from_plumb(&vertex1, &plumb))
+    }
+
+    /// The plain of the base, with its plumb pointing out of the pyramid
+    pub fn base_plain(&self) -> Plain {
+        *self.base_half_space().boundary()
+    }
+
+    /// The plain of the lateral face over the base edge from vertex `edge` to the next one,
+    /// with its plumb pointing out of the pyramid
+    pub fn lateral_face(&self, edge: usize) -> Plain {
+        *self.lateral_half_space(edge).boundary()
+    }
+
+    /// The plains of all lateral faces, in the order of the base edges
+    pub fn lateral_faces(&self) -> Vec<Plain> {
+        (0..self.base.vertices().len()).map(|edge| self.lateral_face(edge)).collect()
+    }
+
+    pub fn base_area(&self) -> f64 {
+        self.base.area()
+    }
+
+    /// The distance between the apex and the plain of the base
+    pub fn height(&self) -> f64 {
+        self.base_plain().distance_from(&self.apex)
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.base_area() * self.height() / 3.0
+    }
+
+    /// Compute the dihedral angle (0 < x < PI) between the base and the lateral face over a given base edge
+    pub fn base_dihedral_angle(&self, edge: usize) -> f64 {
+        HalfSpace::wedge_angle(&self.base_half_space(), &self.lateral_half_space(edge))
+    }
+
+    /// Compute the angle (0 <= x <= PI/2) between the base and the lateral edge from the apex to a given base vertex
+    pub fn lateral_edge_angle(&self, vertex: usize) -> f64 {
+        self.base_plain().angle_with_line(&Line::from_two_points(self.apex, &self.base.vertices()[vertex]))
+    }
+
+    /// Check if a point is inside the pyramid or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        // heights above the base, towards the apex
+        let base_plain = self.base_plain();
+        let (apex_height, height) = (-base_plain.signed_distance_from(&self.apex), -base_plain.signed_distance_from(point));
+        if height < -tolerance::EPSILON || height > apex_height + tolerance::EPSILON {
+            return false;
+        }
+        if tolerance::approx_eq(height, apex_height) {
+            return tolerance::is_zero((*point - self.apex).length());
+        }
+        // project the point from the apex onto the base, and check the projection is in the base polygon
+        let projection = self.apex + (apex_height / (apex_height - height)) * (*point - self.apex);
+        self.base.contains_point(&projection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// A square pyramid over [-1, 1] x [-1, 1] with its apex at height 3
+    fn square_pyramid() -> Pyramid {
+        let base = vec![Vector(-1.0, -1.0, 0.0), Vector(1.0, -1.0, 0.0), Vector(1.0, 1.0, 0.0), Vector(-1.0, 1.0, 0.0)];
+        Pyramid::new(Vector(0.0, 0.0, 3.0), base)
+    }
+
+    #[test]
+    #[should_panic]
+    fn creates_with_apex_on_base() {
+        Pyramid::new(Vector(0.5, 0.5, 0.0), vec![Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0)]);
+    }
+
+    #[test]
+    fn measures() {
+        let pyramid = square_pyramid();
+        assert_eq!(pyramid.base_area(), 4.0);
+        assert_eq!(pyramid.height(), 3.0);
+        assert_eq!(pyramid.volume(), 4.0);
+        assert_eq!(pyramid.base_plain().signed_distance_from(&Vector(0.0, 0.0, -1.0)), 1.0);
+        assert_eq!(pyramid.lateral_faces().len(), 4);
+    }
+
+    #[test]
+    fn angles() {
+        let pyramid = square_pyramid();
+        assert!((pyramid.base_dihedral_angle(0) - 3.0_f64.atan()).abs() < EPSILON);
+        assert!((pyramid.lateral_edge_angle(0) - (3.0 / 2.0_f64.sqrt()).atan()).abs() < EPSILON);
+        let leaning = Pyramid::new(Vector(3.0, 0.0, 1.0), square_pyramid().base().vertices().to_vec());
+        assert!(leaning.base_dihedral_angle(1) > PI / 2.0);
+    }
+
+    #[test]
+    fn contains_points() {
+        let pyramid = square_pyramid();
+        assert!(pyramid.contains_point(&Vector(0.0, 0.0, 1.0)));
+        assert!(pyramid.contains_point(&Vector(1.0, 1.0, 0.0)));
+        assert!(!pyramid.contains_point(&Vector(0.9, 0.9, 1.0)));
+        assert!(!pyramid.contains_point(&Vector(0.0, 0.0, -0.5)));
+    }
+
+    #[test]
+    fn non_convex_base() {
+        // an L-shaped base, whose vertex average (1, 1) is itself a vertex
+        let base = vec![
+            Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0), Vector(2.0, 1.0, 0.0),
+            Vector(1.0, 1.0, 0.0), Vector(1.0, 2.0, 0.0), Vector(0.0, 2.0, 0.0)
+        ];
+        for vertices in [base.clone(), base.iter().rev().copied().collect()] {
+            let pyramid = Pyramid::new(Vector(0.5, 0.5, 3.0), vertices);
+            assert_eq!(pyramid.volume(), 3.0);
+            // every lateral face has the rest of the base behind it, next to its edge
+            for (edge, face) in pyramid.lateral_faces().iter().enumerate() {
+                let vertices = pyramid.base().vertices();
+                let (vertex1, vertex2) = (vertices[edge], vertices[(edge + 1) % vertices.len()]);
+                let midpoint = 0.5 * (vertex1 + vertex2);
+                let base_center = Vector(0.5, 0.5, 0.0);
+                assert!(face.signed_distance_from(&(midpoint + 0.01 * (base_center - midpoint))) < 0.0);
+            }
+            assert!(pyramid.contains_point(&Vector(0.5, 1.5, 0.5)));
+            assert!(pyramid.contains_point(&Vector(1.5, 0.5, 0.0)));
+            assert!(!pyramid.contains_point(&Vector(1.5, 1.5, 0.1)));
+            assert!(pyramid.contains_point(&Vector(0.5, 0.5, 3.0)));
+            assert!(!pyramid.contains_point(&Vector(0.5, 0.5, 3.1)));
+        }
+    }
+}
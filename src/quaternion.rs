@@ -0,0 +1,311 @@
+//! Quaternions, mostly as unit quaternions representing rotations about the origin
+
+use std::ops::Mul;
+
+use crate::{vector::Vector, matrix::Matrix3, affine::Affine3, line::Line, plain::Plain, math::tolerance};
+
+/// A quaternion `scalar + vector.0 i + vector.1 j + vector.2 k`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    pub scalar: f64,
+    pub vector: Vector
+}
+
+impl Quaternion {
+
+    pub fn new(scalar: f64, vector: Vector) -> Quaternion {
+        Quaternion { scalar, vector }
+    }
+
+    /// The quaternion of the identity rotation
+    pub fn identity() -> Quaternion {
+        Quaternion::new(1.0, Vector(0.0, 0.0, 0.0))
+    }
+
+    /// The rotation by an angle about an axis through the origin, counterclockwise when looking against the axis
+    /// # Panics:
+    /// - If the axis is the zero vector
+    pub fn from_axis_angle(axis: &Vector, angle: f64) -> Quaternion {
+        if axis.length() == 0.0 {
+            panic!("Cannot rotate about the zero vector")
+        }
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        Quaternion::new(cos, (sin / axis.length()) * *axis)
+    }
+
+    /// The rotation taking the direction of one vector to the direction of another by the shortest arc.
+    /// Opposite vectors are rotated by a half turn about an arbitrary perpendicular axis
+    /// # Panics:
+    /// - If one of the vectors is the zero vector
+    pub fn shortest_arc(from: &Vector, to: &Vector) -> Quaternion {
+        if tolerance::is_zero(from.length()) || tolerance::is_zero(to.length()) {
+            panic!("The zero vector has no direction to rotate")
+        }
+        let (from, to) = ((1.0 / from.length()) * *from, (1.0 / to.length()) * *to);
+        if tolerance::approx_eq(from * to, -1.0) {
+            return Quaternion::new(0.0, from.any_perpendicular());
+        }
+        // the half-angle quaternion is the normalized (1 + cos, sin * axis)
+        Quaternion::new(1.0 + from * to, Vector::vectoric_product(&from, &to)).normalized()
+    }
+
+    /// The rotation by the roll angle about the x axis, then the pitch angle about the y axis,
+    /// and then the yaw angle about the z axis, all fixed in space
+    pub fn from_euler_angles(roll: f64, pitch: f64, yaw: f64) -> Quaternion {
+        Quaternion::from_axis_angle(&Vector(0.0, 0.0, 1.0), yaw)
+            * Quaternion::from_axis_angle(&Vector(0.0, 1.0, 0.0), pitch)
+            * Quaternion::from_axis_angle(&Vector(1.0, 0.0, 0.0), roll)
+    }
+
+    /// The (roll, pitch, yaw) angles of the rotation, as in `from_euler_angles`,
+    /// with -PI/2 <= pitch <= PI/2. At a pitch of +-PI/2 the roll is chosen to be zero
+    pub fn to_euler_angles(&self) -> (f64, f64, f64) {
+        let Matrix3(row1, row2, row3) = self.to_matrix();
+        let pitch = (-row3.0).clamp(-1.0, 1.0).asin();
+        if tolerance::approx_eq(row3.0.abs(), 1.0) {
+            // gimbal lock: only the sum or the difference of roll and yaw is determined
+            let yaw = (-row1.1).atan2(row2.1);
+            return (0.0, pitch, yaw);
+        }
+        (row3.1.atan2(row3.2), pitch, row2.0.atan2(row1.0))
+    }
+
+    /// The rotation a rotation matrix represents.
+    /// The matrix is assumed to be orthogonal with a determinant of 1
+    pub fn from_matrix(matrix: &Matrix3) -> Quaternion {
+        let Matrix3(Vector(m11, m12, m13), Vector(m21, m22, m23), Vector(m31, m32, m33)) = *matrix;
+        // extract the largest component first, to avoid dividing by a small one
+        let trace = matrix.trace();
+        let quaternion = if trace > 0.0 {
+            let s = 2.0 * (1.0 + trace).sqrt();
+            Quaternion::new(s / 4.0, Vector((m32 - m23) / s, (m13 - m31) / s, (m21 - m12) / s))
+        } else if m11 >= m22 && m11 >= m33 {
+            let s = 2.0 * (1.0 + m11 - m22 - m33).sqrt();
+            Quaternion::new((m32 - m23) / s, Vector(s / 4.0, (m12 + m21) / s, (m13 + m31) / s))
+        } else if m22 >= m33 {
+            let s = 2.0 * (1.0 + m22 - m11 - m33).sqrt();
+            Quaternion::new((m13 - m31) / s, Vector((m12 + m21) / s, s / 4.0, (m23 + m32) / s))
+        } else {
+            let s = 2.0 * (1.0 + m33 - m11 - m22).sqrt();
+            Quaternion::new((m21 - m12) / s, Vector((m13 + m31) / s, (m23 + m32) / s, s / 4.0))
+        };
+        quaternion.normalized()
+    }
+
+    /// The rotation matrix of a unit quaternion
+    pub fn to_matrix(&self) -> Matrix3 {
+        let (w, Vector(x, y, z)) = (self.scalar, self.vector);
+        Matrix3(
+            Vector(1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)),
+            Vector(2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)),
+            Vector(2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y))
+        )
+    }
+
+    /// The axis and angle (0 <= x <= PI) of the rotation of a unit quaternion,
+    /// or None for the identity rotation, which has no axis
+    pub fn to_axis_angle(&self) -> Option<(Vector, f64)> {
+        let sin = self.vector.length();
+        if tolerance::is_zero(sin) {
+            return None;
+        }
+        // q and -q are the same rotation, keep the angle up to a half turn
+        let (axis, cos) = if self.scalar < 0.0 { (-self.vector, -self.scalar) } else { (self.vector, self.scalar) };
+        Some(((1.0 / sin) * axis, 2.0 * sin.atan2(cos)))
+    }
+
+    /// The scalar product of two quaternions as 4-vectors
+    pub fn dot(q1: &Quaternion, q2: &Quaternion) -> f64 {
+        q1.scalar * q2.scalar + q1.vector * q2.vector
+    }
+
+    pub fn norm(&self) -> f64 {
+        Quaternion::dot(self, self).sqrt()
+    }
+
+    /// The unit quaternion in the same direction
+    /// # Panics:
+    /// - If this is the zero quaternion
+    pub fn normalized(&self) -> Quaternion {
+        let norm = self.norm();
+        if norm == 0.0 {
+            panic!("The zero quaternion cannot be normalized")
+        }
+        Quaternion::new(self.scalar / norm, (1.0 / norm) * self.vector)
+    }
+
+    pub fn conjugate(&self) -> Quaternion {
+        Quaternion::new(self.scalar, -self.vector)
+    }
+
+    /// The multiplicative inverse, which is the reverse rotation for unit quaternions.
+    /// Fails for the zero quaternion
+    pub fn inverse(&self) -> Result<Quaternion, ()> {
+        let squared = Quaternion::dot(self, self);
+        if squared == 0.0 {
+            return Err(());
+        }
+        let conjugate = self.conjugate();
+        Ok(Quaternion::new(conjugate.scalar / squared, (1.0 / squared) * conjugate.vector))
+    }
+
+    /// Rotate a vector by a unit quaternion
+    pub fn rotate_vector(&self, vector: &Vector) -> Vector {
+        // q v q* expanded: v + 2w(u x v) + 2u x (u x v)
+        let twice_cross = 2.0 * Vector::vectoric_product(&self.vector, vector);
+        *vector + self.scalar * twice_cross + Vector::vectoric_product(&self.vector, &twice_cross)
+    }
+
+    /// Rotate a line about the origin by a unit quaternion
+    pub fn rotate_line(&self, line: &Line) -> Line {
+        Line::new(self.rotate_vector(&line.point), self.rotate_vector(&line.direction))
+    }
+
+    /// Rotate a plain about the origin by a unit quaternion
+    pub fn rotate_plain(&self, plain: &Plain) -> Plain {
+        // the matrix of a unit quaternion is a rotation, which is never singular
+        Affine3::from_linear(self.to_matrix()).apply_to_plain(plain).expect("A rotation is invertible")
+    }
+
+    /// Interpolate linearly between two unit quaternions and normalize, along the shorter path.
+    /// Faster than `slerp`, but the angular speed is not constant
+    pub fn nlerp(q1: &Quaternion, q2: &Quaternion, t: f64) -> Quaternion {
+        let q2 = if Quaternion::dot(q1, q2) < 0.0 { -1.0 * *q2 } else { *q2 };
+        Quaternion::new(
+            (1.0 - t) * q1.scalar + t * q2.scalar,
+            (1.0 - t) * q1.vector + t * q2.vector
+        ).normalized()
+    }
+
+    /// Interpolate between two unit quaternions along the shorter great arc, at a constant angular speed
+    pub fn slerp(q1: &Quaternion, q2: &Quaternion, t: f64) -> Quaternion {
+        let dot = Quaternion::dot(q1, q2);
+        let (q2, dot) = if dot < 0.0 { (-1.0 * *q2, -dot) } else { (*q2, dot) };
+        if tolerance::approx_eq(dot.min(1.0), 1.0) {
+            // the arc is too short to divide by its sine
+            return Quaternion::nlerp(q1, &q2, t);
+        }
+        let angle = dot.min(1.0).acos();
+        let (a, b) = (((1.0 - t) * angle).sin() / angle.sin(), (t * angle).sin() / angle.sin());
+        Quaternion::new(a * q1.scalar + b * q2.scalar, a * q1.vector + b * q2.vector)
+    }
+}
+
+impl Mul for Quaternion {
+    type Output = Self;
+
+    /// The Hamilton product, composing rotations by applying the right one first
+    fn mul(self, rhs: Self) -> Self::Output {
+        Quaternion::new(
+            self.scalar * rhs.scalar - self.vector * rhs.vector,
+            self.scalar * rhs.vector + rhs.scalar * self.vector + Vector::vectoric_product(&self.vector, &rhs.vector)
+        )
+    }
+}
+
+/// multiply a quaternion by a scalar
+impl Mul<Quaternion> for f64 {
+    type Output = Quaternion;
+
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        Quaternion::new(self * rhs.scalar, self * rhs.vector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close};
+
+    /// Check two unit quaternions represent the same rotation
+    fn assert_same_rotation(q1: &Quaternion, q2: &Quaternion) {
+        assert!((Quaternion::dot(q1, q2).abs() - 1.0).abs() < EPSILON, "{:?} != {:?}", q1, q2);
+    }
+
+    #[test]
+    fn rotates_like_matrices() {
+        let axis = Vector(1.0, -2.0, 0.5);
+        let quaternion = Quaternion::from_axis_angle(&axis, 1.2);
+        let matrix = Matrix3::rotation(&axis, 1.2);
+        let vector = Vector(0.3, 4.0, -1.0);
+        assert_close(&quaternion.rotate_vector(&vector), &(matrix * vector));
+        let Matrix3(row1, row2, row3) = quaternion.to_matrix();
+        for (row, expected) in [row1, row2, row3].iter().zip(matrix.rows().iter()) {
+            assert_close(row, expected);
+        }
+        assert_same_rotation(&Quaternion::from_matrix(&matrix), &quaternion);
+        // a half turn has a zero trace and takes the other branches
+        let half_turn = Quaternion::from_axis_angle(&axis, PI);
+        assert_same_rotation(&Quaternion::from_matrix(&half_turn.to_matrix()), &half_turn);
+
+        let (recovered_axis, angle) = quaternion.to_axis_angle().unwrap();
+        assert_close(&recovered_axis, &((1.0 / axis.length()) * axis));
+        assert!((angle - 1.2).abs() < EPSILON);
+        assert!(Quaternion::identity().to_axis_angle().is_none());
+    }
+
+    #[test]
+    fn composes_and_inverts() {
+        let q1 = Quaternion::from_axis_angle(&Vector(0.0, 0.0, 1.0), PI / 2.0);
+        let q2 = Quaternion::from_axis_angle(&Vector(1.0, 0.0, 0.0), PI / 2.0);
+        let vector = Vector(1.0, 2.0, 3.0);
+        assert_close(&(q1 * q2).rotate_vector(&vector), &q1.rotate_vector(&q2.rotate_vector(&vector)));
+        let inverse = q1.inverse().unwrap();
+        assert_close(&inverse.rotate_vector(&q1.rotate_vector(&vector)), &vector);
+        assert_same_rotation(&(q1 * inverse), &Quaternion::identity());
+        assert_eq!(Quaternion::new(0.0, Vector(0.0, 0.0, 0.0)).inverse(), Err(()));
+    }
+
+    #[test]
+    fn shortest_arc() {
+        let (from, to) = (Vector(2.0, 0.0, 0.0), Vector(0.0, 0.0, 3.0));
+        let arc = Quaternion::shortest_arc(&from, &to);
+        assert_close(&arc.rotate_vector(&from), &Vector(0.0, 0.0, 2.0));
+        assert!((arc.to_axis_angle().unwrap().1 - PI / 2.0).abs() < EPSILON);
+
+        let opposite = Quaternion::shortest_arc(&from, &(-from));
+        assert_close(&opposite.rotate_vector(&from), &(-from));
+        assert_same_rotation(&Quaternion::shortest_arc(&from, &from), &Quaternion::identity());
+    }
+
+    #[test]
+    fn euler_angles() {
+        let (roll, pitch, yaw) = (0.3, -0.5, 2.0);
+        let quaternion = Quaternion::from_euler_angles(roll, pitch, yaw);
+        let (r, p, y) = quaternion.to_euler_angles();
+        assert!((r - roll).abs() < EPSILON && (p - pitch).abs() < EPSILON && (y - yaw).abs() < EPSILON);
+
+        let locked = Quaternion::from_euler_angles(0.4, PI / 2.0, 0.1);
+        let (r, p, y) = locked.to_euler_angles();
+        assert!((p - PI / 2.0).abs() < EPSILON);
+        assert_same_rotation(&Quaternion::from_euler_angles(r, p, y), &locked);
+    }
+
+    #[test]
+    fn rotates_lines_and_plains() {
+        let quarter = Quaternion::from_axis_angle(&Vector(0.0, 0.0, 1.0), PI / 2.0);
+        let line = quarter.rotate_line(&Line::new(Vector(1.0, 0.0, 0.0), Vector(0.0, 0.0, 1.0)));
+        assert!(line.is_on_line(&Vector(0.0, 1.0, 5.0)));
+        let plain = quarter.rotate_plain(&Plain::from_plumb(&Vector(2.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0)));
+        assert!(plain.contains_point(&Vector(0.0, 2.0, 7.0)));
+        assert_close(&plain.unit_normal(), &Vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn interpolates() {
+        let axis = Vector(0.0, 1.0, 0.0);
+        let (q1, q2) = (Quaternion::from_axis_angle(&axis, 0.2), Quaternion::from_axis_angle(&axis, 1.4));
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            let expected = Quaternion::from_axis_angle(&axis, 0.2 + 1.2 * t);
+            assert_same_rotation(&Quaternion::slerp(&q1, &q2, t), &expected);
+        }
+        // nlerp follows the same path, at a different speed
+        assert_same_rotation(&Quaternion::nlerp(&q1, &q2, 0.5), &Quaternion::from_axis_angle(&axis, 0.8));
+        // the shorter path is taken between q and -q representations
+        let negated = -1.0 * q2;
+        assert_same_rotation(&Quaternion::slerp(&q1, &negated, 0.5), &Quaternion::from_axis_angle(&axis, 0.8));
+        assert_same_rotation(&Quaternion::slerp(&q1, &q1, 0.5), &q1);
+    }
+}
@@ -0,0 +1,118 @@
+//! A ray module: half-infinite lines
+
+use crate::{vector::Vector, line::{Line, closest}, plain::{Plain, line_relations::PlainLineRelations}, segment::{self, Segment}};
+
+/// The parameters of the points of a ray, on the line from its origin in its direction
+pub const BOUNDS: (f64, f64) = (0.0, f64::INFINITY);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ray {
+    pub origin: Vector,
+    pub direction: Vector
+}
+
+impl Ray {
+
+    pub fn new(origin: Vector, direction: Vector) -> Ray {
+        Ray { origin, direction }
+    }
+
+    /// Generates a ray from an origin, passing through another point
+    pub fn from_two_points(origin: Vector, other_point: &Vector) -> Ray {
+        Ray::new(origin, *other_point - origin)
+    }
+
+    /// The line containing the ray, such that non-negative parameters are on the ray
+    pub fn line(&self) -> Line {
+        Line::new(self.origin, self.direction)
+    }
+
+    /// The point at a given parameter, where 0 is the origin
+    pub fn point_at(&self, t: f64) -> Vector {
+        self.origin + t * self.direction
+    }
+
+    /// Compute the relation between the ray and a plain.
+    /// Returns None if the line of the ray intersects the plain behind the origin
+    pub fn relation_with_plain(&self, plain: &Plain) -> Option<PlainLineRelations> {
+        let line = self.line();
+        match plain.relation_with_line(&line) {
+            PlainLineRelations::Intersect(point, _) if !closest::within(BOUNDS, closest::parameter_of(&line, &point)) => None,
+            relation => Some(relation)
+        }
+    }
+
+    /// Find the single intersection of the ray and a plain, if existing
+    pub fn intersection_with_plain(&self, plain: &Plain) -> Option<Vector> {
+        match self.relation_with_plain(plain) {
+            Some(PlainLineRelations::Intersect(point, _)) => Some(point),
+            _ => None
+        }
+    }
+
+    /// Find a common point of the ray and a line, if existing
+    pub fn intersection_with_line(&self, line: &Line) -> Option<Vector> {
+        let (s, t) = closest::closest_parameters(&self.line(), BOUNDS, line, closest::UNBOUNDED);
+        segment::common_point(self.point_at(s), line.point + t * line.direction)
+    }
+
+    /// Find a common point of the ray and a segment, if existing
+    pub fn intersection_with_segment(&self, segment: &Segment) -> Option<Vector> {
+        segment.intersection_with_ray(self)
+    }
+
+    /// Find a common point of two rays, if existing
+    pub fn intersection(ray1: &Ray, ray2: &Ray) -> Option<Vector> {
+        let (s, t) = closest::closest_parameters(&ray1.line(), BOUNDS, &ray2.line(), BOUNDS);
+        segment::common_point(ray1.point_at(s), ray2.point_at(t))
+    }
+
+    /// Find the closest point of the ray to a given point.
+    /// Returns the distance and the closest point on the ray
+    pub fn distance_from_point(&self, point: &Vector) -> (f64, Vector) {
+        let t = closest::closest_parameter_to(&self.line(), BOUNDS, point);
+        let closest_point = self.point_at(t);
+        ((*point - closest_point).length(), closest_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xy_plain;
+
+    #[test]
+    fn intersection_with_plain() {
+        let down = Ray::new(Vector(1.0, 2.0, 5.0), Vector(0.0, 0.0, -1.0));
+        assert_eq!(down.intersection_with_plain(&xy_plain()).unwrap(), Vector(1.0, 2.0, 0.0));
+        let up = Ray::new(Vector(1.0, 2.0, 5.0), Vector(0.0, 0.0, 1.0));
+        assert!(up.intersection_with_plain(&xy_plain()).is_none());
+        let inside = Ray::new(Vector(1.0, 2.0, 0.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(inside.relation_with_plain(&xy_plain()), Some(PlainLineRelations::Containing));
+    }
+
+    #[test]
+    fn intersection_with_line() {
+        let ray = Ray::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 1.0, 0.0));
+        let line = Line::new(Vector(3.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0));
+        assert_eq!(ray.intersection_with_line(&line).unwrap(), Vector(3.0, 3.0, 0.0));
+        let behind = Line::new(Vector(-3.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0));
+        assert!(ray.intersection_with_line(&behind).is_none());
+    }
+
+    #[test]
+    fn intersection_of_rays() {
+        let ray1 = Ray::from_two_points(Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0));
+        let ray2 = Ray::new(Vector(4.0, 4.0, 0.0), Vector(0.0, -2.0, 0.0));
+        assert_eq!(Ray::intersection(&ray1, &ray2).unwrap(), Vector(4.0, 0.0, 0.0));
+        let away = Ray::new(Vector(4.0, 4.0, 0.0), Vector(0.0, 2.0, 0.0));
+        assert!(Ray::intersection(&ray1, &away).is_none());
+    }
+
+    #[test]
+    fn distance_from_point() {
+        let ray = Ray::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(ray.distance_from_point(&Vector(7.0, 0.0, 2.0)), (2.0, Vector(7.0, 0.0, 0.0)));
+        assert_eq!(ray.distance_from_point(&Vector(-3.0, 4.0, 0.0)), (5.0, Vector(0.0, 0.0, 0.0)));
+    }
+}
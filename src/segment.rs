@@ -0,0 +1,163 @@
+//! A line segment module
+
+use crate::{vector::Vector, line::{Line, closest}, plain::{Plain, line_relations::PlainLineRelations}, ray::Ray, math::tolerance};
+
+/// The parameters of the points of a segment, on the line from its start to its end
+pub const BOUNDS: (f64, f64) = (0.0, 1.0);
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Segment {
+    pub start: Vector,
+    pub end: Vector
+}
+
+impl Segment {
+
+    pub fn new(start: Vector, end: Vector) -> Segment {
+        Segment { start, end }
+    }
+
+    /// The line containing the segment, such that parameter 0 is the start and parameter 1 is the end
+    pub fn line(&self) -> Line {
+        Line::from_two_points(self.start, &self.end)
+    }
+
+    pub fn length(&self) -> f64 {
+        (self.end - self.start).length()
+    }
+
+    /// The point at a given parameter, where 0 is the start and 1 is the end
+    pub fn point_at(&self, t: f64) -> Vector {
+        self.start + t * (self.end - self.start)
+    }
+
+    /// Compute the relation between the segment and a plain.
+    /// Returns None if the line of the segment intersects the plain outside of the segment
+    pub fn relation_with_plain(&self, plain: &Plain) -> Option<PlainLineRelations> {
+        let line = self.line();
+        match plain.relation_with_line(&line) {
+            PlainLineRelations::Intersect(point, _) if !closest::within(BOUNDS, closest::parameter_of(&line, &point)) => None,
+            relation => Some(relation)
+        }
+    }
+
+    /// Find the single intersection of the segment and a plain, if existing
+    pub fn intersection_with_plain(&self, plain: &Plain) -> Option<Vector> {
+        match self.relation_with_plain(plain) {
+            Some(PlainLineRelations::Intersect(point, _)) => Some(point),
+            _ => None
+        }
+    }
+
+    /// Find a common point of the segment and a line, if existing
+    pub fn intersection_with_line(&self, line: &Line) -> Option<Vector> {
+        let (s, t) = closest::closest_parameters(&self.line(), BOUNDS, line, closest::UNBOUNDED);
+        common_point(self.point_at(s), line.point + t * line.direction)
+    }
+
+    /// Find a common point of the segment and a ray, if existing
+    pub fn intersection_with_ray(&self, ray: &Ray) -> Option<Vector> {
+        let (_, closest_on_segment, closest_on_ray) = self.distance_from_ray(ray);
+        common_point(closest_on_segment, closest_on_ray)
+    }
+
+    /// Find a common point of two segments, if existing
+    pub fn intersection(segment1: &Segment, segment2: &Segment) -> Option<Vector> {
+        let (_, point1, point2) = Segment::distance_between(segment1, segment2);
+        common_point(point1, point2)
+    }
+
+    /// Find the closest point of the segment to a given point.
+    /// Returns the distance and the closest point on the segment
+    pub fn distance_from_point(&self, point: &Vector) -> (f64, Vector) {
+        let t = closest::closest_parameter_to(&self.line(), BOUNDS, point);
+        let closest_point = self.point_at(t);
+        ((*point - closest_point).length(), closest_point)
+    }
+
+    /// Find the closest points of a segment and a ray.
+    /// Returns the distance, the closest point on the segment and the closest point on the ray
+    pub fn distance_from_ray(&self, ray: &Ray) -> (f64, Vector, Vector) {
+        let (s, t) = closest::closest_parameters(&self.line(), BOUNDS, &ray.line(), crate::ray::BOUNDS);
+        let (point1, point2) = (self.point_at(s), ray.point_at(t));
+        ((point2 - point1).length(), point1, point2)
+    }
+
+    /// Find the closest points of two segments.
+    /// Returns the distance, the closest point on the first segment and the closest point on the second
+    pub fn distance_between(segment1: &Segment, segment2: &Segment) -> (f64, Vector, Vector) {
+        let (s, t) = closest::closest_parameters(&segment1.line(), BOUNDS, &segment2.line(), BOUNDS);
+        let (point1, point2) = (segment1.point_at(s), segment2.point_at(t));
+        ((point2 - point1).length(), point1, point2)
+    }
+}
+
+/// A common point of two primitives, given their closest points
+pub(crate) fn common_point(point1: Vector, point2: Vector) -> Option<Vector> {
+    if tolerance::is_zero((point2 - point1).length()) { Some(point1) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xy_plain;
+
+    #[test]
+    fn intersection_with_plain() {
+        let crossing = Segment::new(Vector(1.0, 1.0, -1.0), Vector(1.0, 1.0, 3.0));
+        assert_eq!(crossing.intersection_with_plain(&xy_plain()).unwrap(), Vector(1.0, 1.0, 0.0));
+
+        let above = Segment::new(Vector(1.0, 1.0, 1.0), Vector(1.0, 1.0, 3.0));
+        assert_eq!(above.relation_with_plain(&xy_plain()), None);
+        assert!(above.intersection_with_plain(&xy_plain()).is_none());
+
+        let parallel = Segment::new(Vector(0.0, 0.0, 2.0), Vector(1.0, 1.0, 2.0));
+        assert_eq!(parallel.relation_with_plain(&xy_plain()), Some(PlainLineRelations::Parallel(2.0)));
+    }
+
+    #[test]
+    fn intersection_with_line() {
+        let segment = Segment::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0));
+        let hitting = Line::new(Vector(1.0, 5.0, 0.0), Vector(0.0, 1.0, 0.0));
+        assert_eq!(segment.intersection_with_line(&hitting).unwrap(), Vector(1.0, 0.0, 0.0));
+        let missing = Line::new(Vector(3.0, 5.0, 0.0), Vector(0.0, 1.0, 0.0));
+        assert!(segment.intersection_with_line(&missing).is_none());
+    }
+
+    #[test]
+    fn intersection_of_segments() {
+        let segment1 = Segment::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 2.0, 0.0));
+        let segment2 = Segment::new(Vector(0.0, 2.0, 0.0), Vector(2.0, 0.0, 0.0));
+        assert_eq!(Segment::intersection(&segment1, &segment2).unwrap(), Vector(1.0, 1.0, 0.0));
+        let short = Segment::new(Vector(0.0, 2.0, 0.0), Vector(0.5, 1.5, 0.0));
+        assert!(Segment::intersection(&segment1, &short).is_none());
+    }
+
+    #[test]
+    fn intersection_with_ray() {
+        let segment = Segment::new(Vector(0.0, 0.0, 0.0), Vector(0.0, 0.0, 2.0));
+        let ray = Ray::new(Vector(-3.0, 0.0, 1.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(segment.intersection_with_ray(&ray).unwrap(), Vector(0.0, 0.0, 1.0));
+        let opposite = Ray::new(Vector(-3.0, 0.0, 1.0), Vector(-1.0, 0.0, 0.0));
+        assert!(segment.intersection_with_ray(&opposite).is_none());
+    }
+
+    #[test]
+    fn distance_from_point() {
+        let segment = Segment::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0));
+        assert_eq!(segment.distance_from_point(&Vector(1.0, 3.0, 0.0)), (3.0, Vector(1.0, 0.0, 0.0)));
+        assert_eq!(segment.distance_from_point(&Vector(5.0, 4.0, 0.0)), (5.0, Vector(2.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn distance_between_segments() {
+        let segment1 = Segment::new(Vector(0.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0));
+        let segment2 = Segment::new(Vector(1.0, -1.0, 1.0), Vector(1.0, 1.0, 1.0));
+        assert_eq!(Segment::distance_between(&segment1, &segment2), (1.0, Vector(1.0, 0.0, 0.0), Vector(1.0, 0.0, 1.0)));
+
+        let parallel = Segment::new(Vector(3.0, 1.0, 0.0), Vector(5.0, 1.0, 0.0));
+        let (distance, point1, point2) = Segment::distance_between(&segment1, &parallel);
+        assert_eq!((point1, point2), (Vector(2.0, 0.0, 0.0), Vector(3.0, 1.0, 0.0)));
+        assert_eq!(distance, 2.0_f64.sqrt());
+    }
+}
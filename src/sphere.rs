@@ -0,0 +1,111 @@
+//! A sphere module
+
+use crate::{vector::Vector, matrix::Matrix3, line::Line, plain::Plain, math::tolerance};
+
+use self::{relations::SphereRelations, line_relations::SphereLineRelations, plain_relations::SpherePlainRelations};
+
+pub mod relations;
+pub mod line_relations;
+pub mod plain_relations;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sphere {
+    pub center: Vector,
+    pub radius: f64
+}
+
+impl Sphere {
+
+    pub fn new(center: Vector, radius: f64) -> Sphere {
+        Sphere { center, radius }
+    }
+
+    /// Generates the sphere passing through four points
+    /// # Panics:
+    /// - If the four points are coplanar, and no single sphere passes through them
+    pub fn from_four_points(point1: &Vector, point2: &Vector, point3: &Vector, point4: &Vector) -> Sphere {
+        // the center is equidistant from all points: |c - p1|^2 = |c - pi|^2
+        // simplify: 2(pi - p1) * c = |pi|^2 - |p1|^2
+        let (u, v, w) = (*point2 - *point1, *point3 - *point1, *point4 - *point1);
+        if Vector::are_lindep(&u, &v, &w) {
+            panic!("Four coplanar points cannot determine a sphere")
+        }
+        let constant = |point: &Vector| ((*point) * (*point) - (*point1) * (*point1)) / 2.0;
+        let system = Matrix3(u, v, w);
+        let center = system.solve(&Vector(constant(point2), constant(point3), constant(point4))).unwrap();
+        Sphere::new(center, (*point1 - center).length())
+    }
+
+    /// Generates the sphere with a given center, tangent to a given plain
+    /// # Panics:
+    /// - If the center is on the plain
+    pub fn tangent_to_plain(center: Vector, plain: &Plain) -> Sphere {
+        let radius = plain.distance_from(&center);
+        if tolerance::is_zero(radius) {
+            panic!("A sphere centered on a plain cannot be tangent to it")
+        }
+        Sphere::new(center, radius)
+    }
+
+    /// Check if a point is inside the sphere or on its surface
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        (*point - self.center).length() < self.radius + tolerance::EPSILON
+    }
+
+    /// Check if a point is on the surface of the sphere
+    pub fn is_on_surface(&self, point: &Vector) -> bool {
+        tolerance::approx_eq((*point - self.center).length(), self.radius)
+    }
+
+    /// Compute the relation between the sphere and a plain
+    pub fn relation_with_plain(&self, plain: &Plain) -> SpherePlainRelations {
+        SpherePlainRelations::of(self, plain)
+    }
+
+    /// Compute the relation between the sphere and a line
+    pub fn relation_with_line(&self, line: &Line) -> SphereLineRelations {
+        SphereLineRelations::of(self, line)
+    }
+
+    /// Compute the relation between two spheres
+    pub fn relation_between(sphere1: &Sphere, sphere2: &Sphere) -> SphereRelations {
+        SphereRelations::of(sphere1, sphere2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn creates_from_four_points() {
+        let points = [Vector(3.0, 2.0, 1.0), Vector(1.0, 4.0, 1.0), Vector(1.0, 2.0, 3.0), Vector(-1.0, 2.0, 1.0)];
+        let sphere = Sphere::from_four_points(&points[0], &points[1], &points[2], &points[3]);
+        assert_eq!(sphere, Sphere::new(Vector(1.0, 2.0, 1.0), 2.0));
+        assert!(points.iter().all(|point| sphere.is_on_surface(point)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn creates_from_coplanar_points() {
+        Sphere::from_four_points(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0), &Vector(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn creates_tangent_to_plain() {
+        let plain = Plain::from_three_points(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0)); // z=0
+        let sphere = Sphere::tangent_to_plain(Vector(1.0, 1.0, -3.0), &plain);
+        assert_eq!(sphere.radius, 3.0);
+        assert_eq!(sphere.relation_with_plain(&plain), SpherePlainRelations::Tangent(Vector(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn contains_points() {
+        let sphere = Sphere::new(Vector(0.0, 0.0, 0.0), 2.0);
+        assert!(sphere.contains_point(&Vector(1.0, 1.0, 1.0)));
+        assert!(sphere.contains_point(&Vector(0.0, 2.0, 0.0)));
+        assert!(!sphere.contains_point(&Vector(2.0, 2.0, 0.0)));
+        assert!(sphere.is_on_surface(&Vector(0.0, 2.0, 0.0)));
+        assert!(!sphere.is_on_surface(&Vector(1.0, 1.0, 1.0)));
+    }
+}
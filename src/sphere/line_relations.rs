@@ -0,0 +1,59 @@
+//! A module to describe a relation between a sphere and a line
+
+use crate::{vector::Vector, line::Line, math::tolerance};
+
+use super::Sphere;
+
+#[derive(Debug, PartialEq)]
+pub enum SphereLineRelations {
+    /// The line doesn't touch the sphere, and is at a given distance from its surface
+    Apart(f64),
+    /// The line touches the sphere at a single point
+    Tangent(Vector),
+    /// The line crosses the sphere at two points, ordered along the direction of the line
+    Secant(Vector, Vector)
+}
+
+impl SphereLineRelations {
+    /// find the relation between a sphere and a line
+    pub fn of(sphere: &Sphere, line: &Line) -> SphereLineRelations {
+        let projection = line.projection_of(&sphere.center);
+        let distance = (projection - sphere.center).length();
+        if tolerance::approx_eq(distance, sphere.radius) {
+            Self::Tangent(projection)
+        } else if distance > sphere.radius {
+            Self::Apart(distance - sphere.radius)
+        } else {
+            // the secant points are equidistant from the projection of the center
+            let half_chord = (sphere.radius * sphere.radius - distance * distance).sqrt();
+            let offset = (half_chord / line.direction.length()) * line.direction;
+            Self::Secant(projection - offset, projection + offset)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apart() {
+        let sphere = Sphere::new(Vector(0.0, 0.0, 0.0), 1.0);
+        let line = Line::new(Vector(0.0, 3.0, 0.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(SphereLineRelations::of(&sphere, &line), SphereLineRelations::Apart(2.0))
+    }
+
+    #[test]
+    fn tangent() {
+        let sphere = Sphere::new(Vector(0.0, 0.0, 0.0), 1.0);
+        let line = Line::new(Vector(5.0, 0.0, 1.0), Vector(1.0, 0.0, 0.0));
+        assert_eq!(SphereLineRelations::of(&sphere, &line), SphereLineRelations::Tangent(Vector(0.0, 0.0, 1.0)))
+    }
+
+    #[test]
+    fn secant() {
+        let sphere = Sphere::new(Vector(1.0, 0.0, 0.0), 5.0);
+        let line = Line::new(Vector(0.0, 3.0, 0.0), Vector(-2.0, 0.0, 0.0));
+        assert_eq!(SphereLineRelations::of(&sphere, &line), SphereLineRelations::Secant(Vector(5.0, 3.0, 0.0), Vector(-3.0, 3.0, 0.0)))
+    }
+}
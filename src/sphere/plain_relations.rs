@@ -0,0 +1,56 @@
+//! A module to describe a relation between a sphere and a plain
+
+use crate::{vector::Vector, plain::Plain, math::tolerance};
+
+use super::Sphere;
+
+#[derive(Debug, PartialEq)]
+pub enum SpherePlainRelations {
+    /// The plain doesn't touch the sphere, and is at a given distance from its surface
+    Apart(f64),
+    /// The plain touches the sphere at a single point
+    Tangent(Vector),
+    /// The plain cuts the sphere in a circle, with a given center and radius
+    Intersect(Vector, f64)
+}
+
+impl SpherePlainRelations {
+    /// find the relation between a sphere and a plain
+    pub fn of(sphere: &Sphere, plain: &Plain) -> SpherePlainRelations {
+        let distance = plain.distance_from(&sphere.center);
+        let projection = plain.projection_of(&sphere.center);
+        if tolerance::approx_eq(distance, sphere.radius) {
+            Self::Tangent(projection)
+        } else if distance > sphere.radius {
+            Self::Apart(distance - sphere.radius)
+        } else {
+            // the radius of the circle is a leg of a right triangle with the sphere radius as hypotenuse
+            let radius = (sphere.radius * sphere.radius - distance * distance).sqrt();
+            Self::Intersect(projection, radius)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::xy_plain;
+
+    #[test]
+    fn apart() {
+        let sphere = Sphere::new(Vector(0.0, 0.0, 5.0), 2.0);
+        assert_eq!(SpherePlainRelations::of(&sphere, &xy_plain()), SpherePlainRelations::Apart(3.0))
+    }
+
+    #[test]
+    fn tangent() {
+        let sphere = Sphere::new(Vector(1.0, 2.0, 2.0), 2.0);
+        assert_eq!(SpherePlainRelations::of(&sphere, &xy_plain()), SpherePlainRelations::Tangent(Vector(1.0, 2.0, 0.0)))
+    }
+
+    #[test]
+    fn intersect() {
+        let sphere = Sphere::new(Vector(1.0, 2.0, 3.0), 5.0);
+        assert_eq!(SpherePlainRelations::of(&sphere, &xy_plain()), SpherePlainRelations::Intersect(Vector(1.0, 2.0, 0.0), 4.0))
+    }
+}
@@ -0,0 +1,94 @@
+//! A module to describe a relation between two spheres
+
+use crate::{vector::Vector, math::tolerance};
+
+use super::Sphere;
+
+#[derive(Debug, PartialEq)]
+pub enum SphereRelations {
+    /// The two spheres share the exact same points
+    Unite,
+    /// Each sphere is outside of the other, with a given distance between their surfaces
+    Apart(f64),
+    /// One sphere is inside the other, with a given distance between their surfaces
+    Contained(f64),
+    /// The spheres touch from outside at a single point
+    TangentExternally(Vector),
+    /// The smaller sphere touches the larger one from inside at a single point
+    TangentInternally(Vector),
+    /// The spheres cut each other in a circle with a given center and radius,
+    /// perpendicular to the line of the centers
+    Intersect(Vector, f64)
+}
+
+impl SphereRelations {
+    /// find the relation between two spheres
+    pub fn of(sphere1: &Sphere, sphere2: &Sphere) -> SphereRelations {
+        let centers = sphere2.center - sphere1.center;
+        let distance = centers.length();
+        let (r1, r2) = (sphere1.radius, sphere2.radius);
+
+        if tolerance::is_zero(distance) {
+            return if tolerance::approx_eq(r1, r2) { Self::Unite } else { Self::Contained((r1 - r2).abs()) };
+        }
+
+        // the point at a given distance from the first center, towards the second one
+        let towards_second = |length: f64| sphere1.center + (length / distance) * centers;
+        if tolerance::approx_eq(distance, r1 + r2) {
+            Self::TangentExternally(towards_second(r1))
+        } else if distance > r1 + r2 {
+            Self::Apart(distance - r1 - r2)
+        } else if tolerance::approx_eq(distance, (r1 - r2).abs()) {
+            // the touching point is on the far side of the smaller sphere from the larger center
+            if r1 > r2 { Self::TangentInternally(towards_second(r1)) } else { Self::TangentInternally(towards_second(-r1)) }
+        } else if distance < (r1 - r2).abs() {
+            Self::Contained((r1 - r2).abs() - distance)
+        } else {
+            // the circle is at distance a from the first center, where r1^2 - a^2 = r2^2 - (d - a)^2
+            let a = (distance * distance + r1 * r1 - r2 * r2) / (2.0 * distance);
+            Self::Intersect(towards_second(a), (r1 * r1 - a * a).sqrt())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_spheres() {
+        let sphere = Sphere::new(Vector(1.0, 1.0, 1.0), 2.0);
+        assert_eq!(SphereRelations::of(&sphere, &sphere), SphereRelations::Unite)
+    }
+
+    #[test]
+    fn apart_spheres() {
+        let sphere1 = Sphere::new(Vector(0.0, 0.0, 0.0), 1.0);
+        let sphere2 = Sphere::new(Vector(5.0, 0.0, 0.0), 2.0);
+        assert_eq!(SphereRelations::of(&sphere1, &sphere2), SphereRelations::Apart(2.0))
+    }
+
+    #[test]
+    fn contained_spheres() {
+        let sphere1 = Sphere::new(Vector(0.0, 0.0, 0.0), 5.0);
+        let sphere2 = Sphere::new(Vector(1.0, 0.0, 0.0), 2.0);
+        assert_eq!(SphereRelations::of(&sphere1, &sphere2), SphereRelations::Contained(2.0))
+    }
+
+    #[test]
+    fn tangent_spheres() {
+        let sphere1 = Sphere::new(Vector(0.0, 0.0, 0.0), 1.0);
+        let outside = Sphere::new(Vector(0.0, 3.0, 0.0), 2.0);
+        assert_eq!(SphereRelations::of(&sphere1, &outside), SphereRelations::TangentExternally(Vector(0.0, 1.0, 0.0)));
+        let around = Sphere::new(Vector(0.0, 1.0, 0.0), 2.0);
+        assert_eq!(SphereRelations::of(&sphere1, &around), SphereRelations::TangentInternally(Vector(0.0, -1.0, 0.0)));
+        assert_eq!(SphereRelations::of(&around, &sphere1), SphereRelations::TangentInternally(Vector(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn intersecting_spheres() {
+        let sphere1 = Sphere::new(Vector(0.0, 0.0, 0.0), 5.0);
+        let sphere2 = Sphere::new(Vector(0.0, 0.0, 6.0), 5.0);
+        assert_eq!(SphereRelations::of(&sphere1, &sphere2), SphereRelations::Intersect(Vector(0.0, 0.0, 3.0), 4.0))
+    }
+}
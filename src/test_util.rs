@@ -0,0 +1,15 @@
+//! Fixtures shared by the tests of several modules
+
+use crate::{vector::Vector, plain::Plain};
+
+/// The tolerance for comparing computed values in tests
+pub const EPSILON: f64 = 0.00001;
+
+pub fn assert_close(u: &Vector, v: &Vector) {
+    assert!((*u - *v).length() < EPSILON, "{:?} != {:?}", u, v);
+}
+
+/// The plain z = 0, with its plumb along the positive z axis
+pub fn xy_plain() -> Plain {
+    Plain::from_three_points(&Vector(0.0, 0.0, 0.0), &Vector(1.0, 0.0, 0.0), &Vector(0.0, 1.0, 0.0))
+}
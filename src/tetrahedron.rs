@@ -0,0 +1,188 @@
+//! A tetrahedron module
+
+use crate::{vector::Vector, line::Line, plain::{Plain, half_space::HalfSpace}, sphere::Sphere};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Tetrahedron {
+    pub vertices: [Vector; 4]
+}
+
+impl Tetrahedron {
+
+    /// Generates a tetrahedron from its four vertices
+    /// # Panics:
+    /// - If the four vertices are coplanar
+    pub fn new(a: Vector, b: Vector, c: Vector, d: Vector) -> Tetrahedron {
+        if Vector::are_lindep(&(b - a), &(c - a), &(d - a)) {
+            panic!("Four coplanar points cannot form a tetrahedron")
+        }
+        Tetrahedron { vertices: [a, b, c, d] }
+    }
+
+    /// The three vertices other than a given one, forming the face opposite to it
+    fn face_vertices(&self, opposite: usize) -> [Vector; 3] {
+        let mut others = self.vertices.iter().enumerate()
+            .filter(|(i, _)| *i != opposite)
+            .map(|(_, vertex)| *vertex);
+        [others.next().unwrap(), others.next().unwrap(), others.next().unwrap()]
+    }
+
+    /// The half-space bounded by the face opposite to a given vertex, containing the tetrahedron
+    fn face_half_space(&self, opposite: usize) -> HalfSpace {
+        let [p1, p2, p3] = self.face_vertices(opposite);
+        HalfSpace::containing(Plain::from_three_points(&p1, &p2, &p3), &self.vertices[opposite])
+    }
+
+    /// The indices of the two vertices not on a given edge, which are the opposite vertices of the faces sharing the edge
+    /// # Panics:
+    /// - If the two vertex indices are the same, and do not form an edge
+    fn faces_of_edge(vertex1: usize, vertex2: usize) -> (usize, usize) {
+        if vertex1 == vertex2 {
+            panic!("An edge must connect two different vertices")
+        }
+        let mut others = (0..4).filter(|i| *i != vertex1 && *i != vertex2);
+        (others.next().unwrap(), others.next().unwrap())
+    }
+
+    pub fn volume(&self) -> f64 {
+        let [a, b, c, d] = self.vertices;
+        Vector::tetrahedron_volume_of_points(&a, &b, &c, &d).abs()
+    }
+
+    /// The area of the face opposite to a given vertex
+    pub fn face_area(&self, opposite: usize) -> f64 {
+        let [p1, p2, p3] = self.face_vertices(opposite);
+        Vector::triangle_area_of_points(&p1, &p2, &p3)
+    }
+
+    /// The plain of the face opposite to a given vertex, with its plumb pointing out of the tetrahedron
+    pub fn face(&self, opposite: usize) -> Plain {
+        *self.face_half_space(opposite).boundary()
+    }
+
+    /// The plains of the faces, with their plumbs pointing out of the tetrahedron.
+    /// Face i is opposite to vertex i
+    pub fn faces(&self) -> [Plain; 4] {
+        [self.face(0), self.face(1), self.face(2), self.face(3)]
+    }
+
+    /// Compute the dihedral angle (0 < x < PI) at the edge connecting two given vertices
+    pub fn dihedral_angle(&self, vertex1: usize, vertex2: usize) -> f64 {
+        let (face1, face2) = Tetrahedron::faces_of_edge(vertex1, vertex2);
+        HalfSpace::wedge_angle(&self.face_half_space(face1), &self.face_half_space(face2))
+    }
+
+    /// Compute the angle (0 <= x <= PI/2) between the edge connecting two given vertices
+    /// and the face opposite to a given vertex
+    pub fn edge_face_angle(&self, vertex1: usize, vertex2: usize, opposite: usize) -> f64 {
+        let edge = Line::from_two_points(self.vertices[vertex1], &self.vertices[vertex2]);
+        self.face(opposite).angle_with_line(&edge)
+    }
+
+    /// The height from a given vertex to the face opposite to it
+    pub fn height(&self, vertex: usize) -> f64 {
+        self.face(vertex).distance_from(&self.vertices[vertex])
+    }
+
+    /// The heights from each vertex to the face opposite to it
+    pub fn heights(&self) -> [f64; 4] {
+        [self.height(0), self.height(1), self.height(2), self.height(3)]
+    }
+
+    /// The sphere passing through the four vertices
+    pub fn circumscribed_sphere(&self) -> Sphere {
+        let [a, b, c, d] = self.vertices;
+        Sphere::from_four_points(&a, &b, &c, &d)
+    }
+
+    /// The sphere tangent to the four faces
+    pub fn inscribed_sphere(&self) -> Sphere {
+        // the center is the average of the vertices, weighted by the areas of the opposite faces,
+        // and the radius follows from V = r * (total area) / 3
+        let areas = [self.face_area(0), self.face_area(1), self.face_area(2), self.face_area(3)];
+        let total_area: f64 = areas.iter().sum();
+        let weighted = areas.iter().zip(self.vertices.iter())
+            .fold(Vector(0.0, 0.0, 0.0), |sum, (area, vertex)| sum + (*area) * (*vertex));
+        Sphere::new((1.0 / total_area) * weighted, 3.0 * self.volume() / total_area)
+    }
+
+    /// Check if a point is inside the tetrahedron or on its boundary
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        (0..4).all(|face| self.face_half_space(face).contains(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::EPSILON;
+
+    /// The corner tetrahedron cut off the first octant by x + y + z = 1
+    fn corner() -> Tetrahedron {
+        Tetrahedron::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0))
+    }
+
+    #[test]
+    #[should_panic]
+    fn creates_from_coplanar_points() {
+        Tetrahedron::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn volume_and_heights() {
+        let tetrahedron = corner();
+        assert!((tetrahedron.volume() - 1.0 / 6.0).abs() < EPSILON);
+        let heights = tetrahedron.heights();
+        assert!((heights[0] - 1.0 / 3.0_f64.sqrt()).abs() < EPSILON);
+        assert_eq!(&heights[1..], &[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn outward_faces() {
+        let tetrahedron = corner();
+        let inside = Vector(0.1, 0.1, 0.1);
+        assert!(tetrahedron.faces().iter().all(|face| face.signed_distance_from(&inside) < 0.0));
+        assert_eq!(tetrahedron.face(3).signed_distance_from(&Vector(0.0, 0.0, -2.0)), 2.0);
+    }
+
+    #[test]
+    fn angles() {
+        let tetrahedron = corner();
+        // the coordinate faces meet at right angles, and the slanted face meets them at acos(1/sqrt(3))
+        assert!((tetrahedron.dihedral_angle(0, 3) - PI / 2.0).abs() < EPSILON);
+        assert!((tetrahedron.dihedral_angle(1, 2) - (1.0 / 3.0_f64.sqrt()).acos()).abs() < EPSILON);
+        assert!((tetrahedron.edge_face_angle(0, 3, 3) - PI / 2.0).abs() < EPSILON);
+        assert!((tetrahedron.edge_face_angle(1, 3, 3) - PI / 4.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn obtuse_dihedral_angle() {
+        // a flat tetrahedron over a wide base has an obtuse angle at its top edge
+        let tetrahedron = Tetrahedron::new(Vector(-2.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0), Vector(0.0, -1.0, 1.0), Vector(0.0, 1.0, 1.0));
+        assert!(tetrahedron.dihedral_angle(2, 3) > PI / 2.0);
+    }
+
+    #[test]
+    fn spheres() {
+        let tetrahedron = corner();
+        let circumscribed = tetrahedron.circumscribed_sphere();
+        assert!(tetrahedron.vertices.iter().all(|vertex| circumscribed.is_on_surface(vertex)));
+
+        let inscribed = tetrahedron.inscribed_sphere();
+        let radius = 1.0 / (3.0 + 3.0_f64.sqrt());
+        assert!((inscribed.radius - radius).abs() < EPSILON);
+        assert!((inscribed.center - Vector(radius, radius, radius)).length() < EPSILON);
+        assert!(tetrahedron.faces().iter().all(|face| (face.distance_from(&inscribed.center) - radius).abs() < EPSILON));
+    }
+
+    #[test]
+    fn contains_points() {
+        let tetrahedron = corner();
+        assert!(tetrahedron.contains_point(&Vector(0.2, 0.2, 0.2)));
+        assert!(tetrahedron.contains_point(&Vector(0.0, 0.5, 0.5)));
+        assert!(!tetrahedron.contains_point(&Vector(0.5, 0.5, 0.5)));
+        assert!(!tetrahedron.contains_point(&Vector(-0.1, 0.2, 0.2)));
+    }
+}
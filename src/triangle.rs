@@ -0,0 +1,220 @@
+//! A triangle module
+
+use crate::{vector::Vector, plain::Plain, ray::Ray, math::tolerance};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Triangle {
+    pub a: Vector,
+    pub b: Vector,
+    pub c: Vector
+}
+
+impl Triangle {
+
+    pub fn new(a: Vector, b: Vector, c: Vector) -> Triangle {
+        Triangle { a, b, c }
+    }
+
+    pub fn area(&self) -> f64 {
+        Vector::triangle_area_of_points(&self.a, &self.b, &self.c)
+    }
+
+    /// Check if the vertices are collinear, up to the tolerance on the sine of the angle at `a`,
+    /// so that the triangle doesn't span a plane
+    pub fn is_degenerate(&self) -> bool {
+        (self.b - self.a).is_lindep(&(self.c - self.a))
+    }
+
+    /// Compute a unit vector perpendicular to the triangle, oriented by the order `a, b, c` using the right hand rule
+    /// # Panics:
+    /// - If the vertices are collinear and don't form a plane
+    pub fn normal(&self) -> Vector {
+        if self.is_degenerate() {
+            panic!("A degenerate triangle has no normal")
+        }
+        let normal = Vector::vectoric_product(&(self.b - self.a), &(self.c - self.a));
+        (1.0 / normal.length()) * normal
+    }
+
+    /// The plain containing the triangle, with the same orientation as its normal
+    /// # Panics:
+    /// - If the vertices are collinear and don't form a plane
+    pub fn plain(&self) -> Plain {
+        Plain::from_three_points(&self.a, &self.b, &self.c)
+    }
+
+    /// The lengths of the sides opposite to `a`, `b` and `c`
+    pub fn side_lengths(&self) -> (f64, f64, f64) {
+        ((self.c - self.b).length(), (self.a - self.c).length(), (self.b - self.a).length())
+    }
+
+    /// The interior angles at `a`, `b` and `c`, in radians
+    pub fn angles(&self) -> (f64, f64, f64) {
+        let angle_at = |vertex: Vector, other1: Vector, other2: Vector| Vector::angle_between(&(other1 - vertex), &(other2 - vertex));
+        (angle_at(self.a, self.b, self.c), angle_at(self.b, self.c, self.a), angle_at(self.c, self.a, self.b))
+    }
+
+    /// The intersection of the medians
+    pub fn centroid(&self) -> Vector {
+        (1.0 / 3.0) * (self.a + self.b + self.c)
+    }
+
+    /// The center of the circle passing through the three vertices
+    /// # Panics:
+    /// - If the vertices are collinear
+    pub fn circumcenter(&self) -> Vector {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        if self.is_degenerate() {
+            panic!("A degenerate triangle has no circumcenter")
+        }
+        let normal = Vector::vectoric_product(&ab, &ac);
+        let normal_squared = normal * normal;
+        // a + (|ac|^2 * (n x ab) + |ab|^2 * (ac x n)) / 2|n|^2, where n = ab x ac
+        let offset = (ac * ac) * Vector::vectoric_product(&normal, &ab) + (ab * ab) * Vector::vectoric_product(&ac, &normal);
+        self.a + (1.0 / (2.0 * normal_squared)) * offset
+    }
+
+    /// The center of the circle tangent to the three sides
+    pub fn incenter(&self) -> Vector {
+        // the average of the vertices, weighted by the opposite side lengths
+        let (length_a, length_b, length_c) = self.side_lengths();
+        let perimeter = length_a + length_b + length_c;
+        (1.0 / perimeter) * (length_a * self.a + length_b * self.b + length_c * self.c)
+    }
+
+    /// The intersection of the altitudes
+    /// # Panics:
+    /// - If the vertices are collinear
+    pub fn orthocenter(&self) -> Vector {
+        // on the Euler line: h = a + b + c - 2 * circumcenter
+        self.a + self.b + self.c - 2.0 * self.circumcenter()
+    }
+
+    /// Compute the barycentric coordinates `(u, v, w)` of a point, such that its projection on the plain
+    /// of the triangle is `u*a + v*b + w*c` with `u + v + w = 1`
+    /// # Panics:
+    /// - If the vertices are collinear
+    pub fn barycentric(&self, point: &Vector) -> (f64, f64, f64) {
+        if self.is_degenerate() {
+            panic!("A degenerate triangle has no barycentric coordinates")
+        }
+        let (ab, ac, ap) = (self.b - self.a, self.c - self.a, *point - self.a);
+        let (d00, d01, d11) = (ab * ab, ab * ac, ac * ac);
+        let (d20, d21) = (ap * ab, ap * ac);
+        let denominator = d00 * d11 - d01 * d01;
+        let v = (d11 * d20 - d01 * d21) / denominator;
+        let w = (d00 * d21 - d01 * d20) / denominator;
+        (1.0 - v - w, v, w)
+    }
+
+    /// Check if a point lies in the triangle, including its sides.
+    /// A degenerate triangle, with collinear vertices, contains no points
+    pub fn contains_point(&self, point: &Vector) -> bool {
+        if self.is_degenerate() || !self.plain().contains_point(point) {
+            return false;
+        }
+        let (u, v, w) = self.barycentric(point);
+        [u, v, w].iter().all(|coordinate| *coordinate > -tolerance::EPSILON)
+    }
+
+    /// Find the intersection of a ray with the triangle, using the Möller–Trumbore algorithm.
+    /// A ray that lies in the plain of the triangle does not intersect it
+    pub fn intersection_with_ray(&self, ray: &Ray) -> Option<Vector> {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        let p = Vector::vectoric_product(&ray.direction, &ac);
+        let determinant = ab * p;
+        if tolerance::is_zero(determinant) {
+            return None;
+        }
+
+        let origin_offset = ray.origin - self.a;
+        let u = origin_offset * p / determinant;
+        if !(-tolerance::EPSILON..=1.0 + tolerance::EPSILON).contains(&u) {
+            return None;
+        }
+        let q = Vector::vectoric_product(&origin_offset, &ab);
+        let v = ray.direction * q / determinant;
+        if v < -tolerance::EPSILON || u + v > 1.0 + tolerance::EPSILON {
+            return None;
+        }
+        let t = ac * q / determinant;
+        if t < -tolerance::EPSILON { None } else { Some(ray.point_at(t)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::test_util::{EPSILON, assert_close};
+
+    fn right_triangle() -> Triangle {
+        Triangle::new(Vector(0.0, 0.0, 0.0), Vector(4.0, 0.0, 0.0), Vector(0.0, 3.0, 0.0))
+    }
+
+    #[test]
+    fn measures() {
+        let triangle = right_triangle();
+        assert_eq!(triangle.area(), 6.0);
+        assert_eq!(triangle.normal(), Vector(0.0, 0.0, 1.0));
+        assert_eq!(triangle.side_lengths(), (5.0, 3.0, 4.0));
+        let (alpha, beta, gamma) = triangle.angles();
+        assert_eq!(alpha, PI / 2.0);
+        assert!((alpha + beta + gamma - PI).abs() < EPSILON);
+        assert!(triangle.plain().contains_point(&Vector(7.0, -2.0, 0.0)));
+    }
+
+    #[test]
+    fn centers_of_right_triangle() {
+        let triangle = right_triangle();
+        assert_close(&triangle.centroid(), &Vector(4.0 / 3.0, 1.0, 0.0));
+        assert_close(&triangle.circumcenter(), &Vector(2.0, 1.5, 0.0));
+        assert_close(&triangle.incenter(), &Vector(1.0, 1.0, 0.0));
+        assert_close(&triangle.orthocenter(), &Vector(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn centers_in_space() {
+        // an equilateral triangle, where all centers coincide
+        let triangle = Triangle::new(Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        let center = Vector(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0);
+        assert_close(&triangle.circumcenter(), &center);
+        assert_close(&triangle.incenter(), &center);
+        assert_close(&triangle.orthocenter(), &center);
+    }
+
+    #[test]
+    fn barycentric_coordinates() {
+        let triangle = right_triangle();
+        assert_eq!(triangle.barycentric(&Vector(0.0, 0.0, 0.0)), (1.0, 0.0, 0.0));
+        assert_eq!(triangle.barycentric(&Vector(2.0, 1.5, 5.0)), (0.0, 0.5, 0.5));
+        assert!(triangle.contains_point(&Vector(1.0, 1.0, 0.0)));
+        assert!(triangle.contains_point(&Vector(2.0, 1.5, 0.0)));
+        assert!(!triangle.contains_point(&Vector(3.0, 3.0, 0.0)));
+        assert!(!triangle.contains_point(&Vector(1.0, 1.0, 1.0)));
+        let degenerate = Triangle::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(2.0, 0.0, 0.0));
+        assert!(!degenerate.contains_point(&Vector(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn small_triangle() {
+        let triangle = Triangle::new(Vector(0.0, 0.0, 0.0), Vector(1e-2, 0.0, 0.0), Vector(0.0, 1e-3, 0.0));
+        assert!(!triangle.is_degenerate());
+        assert_close(&triangle.circumcenter(), &Vector(5e-3, 5e-4, 0.0));
+        assert!(triangle.contains_point(&Vector(1e-3, 1e-4, 0.0)));
+        assert!(!triangle.contains_point(&Vector(1e-2, 1e-3, 0.0)));
+        assert!(Triangle::new(Vector(0.0, 0.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(2.0, 1e-12, 0.0)).is_degenerate());
+    }
+
+    #[test]
+    fn ray_intersection() {
+        let triangle = right_triangle();
+        let hitting = Ray::new(Vector(1.0, 1.0, 5.0), Vector(0.0, 0.0, -1.0));
+        assert_eq!(triangle.intersection_with_ray(&hitting).unwrap(), Vector(1.0, 1.0, 0.0));
+        let away = Ray::new(Vector(1.0, 1.0, 5.0), Vector(0.0, 0.0, 1.0));
+        assert!(triangle.intersection_with_ray(&away).is_none());
+        let missing = Ray::new(Vector(3.0, 3.0, 5.0), Vector(0.0, 0.0, -1.0));
+        assert!(triangle.intersection_with_ray(&missing).is_none());
+    }
+}
@@ -1,6 +1,8 @@
-use std::ops::{Add, Sub, Mul, Div};
+use std::ops::{Add, Sub, Mul, Div, Neg};
 
-use crate::math::ratio::Ratio;
+use crate::{matrix::Matrix3, math::{ratio::Ratio, tolerance}};
+
+pub mod measures;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Vector(pub f64, pub f64, pub f64);
@@ -12,10 +14,68 @@ impl Vector {
         ((*self) * (*self)).sqrt()
     }
 
-    /// Check if two vectors are linearly dependent
+    /// Check if two vectors are linearly dependent, up to the tolerance
     pub fn is_lindep(&self, other: &Vector) -> bool {
-        let division = *self / *other;
-        division.is_ok()
+        let scale = self.length() * other.length();
+        if scale == 0.0 {
+            return true;
+        }
+        // compare the sine of the angle rather than the area, so the test doesn't depend on the lengths
+        tolerance::is_zero(Vector::vectoric_product(self, other).length() / scale)
+    }
+
+    /// Check if three vectors are linearly dependent, i.e. parallel to a single plane
+    pub fn are_lindep(u: &Vector, v: &Vector, w: &Vector) -> bool {
+        let scale = u.length() * v.length() * w.length();
+        if scale == 0.0 {
+            return true;
+        }
+        let determinant = Vector::triple_product(u, v, w);
+        // compare the sine of the angles rather than the volume, so the test doesn't depend on the lengths
+        tolerance::is_zero(determinant / scale)
+    }
+
+    /// Decompose this vector as a linear combination `a*u + b*v + c*w`, and return `(a, b, c)`.
+    /// Fails if the three vectors are linearly dependent and don't form a basis
+    pub fn decompose(&self, u: &Vector, v: &Vector, w: &Vector) -> Result<(f64, f64, f64), ()> {
+        // the coefficients solve the system whose matrix has the columns u, v, w
+        let Vector(a, b, c) = Matrix3::from_columns(u, v, w).solve(self)?;
+        Ok((a, b, c))
+    }
+
+    /// Find a unit vector perpendicular to this one
+    /// # Panics:
+    /// - If this is the zero vector
+    pub fn any_perpendicular(&self) -> Vector {
+        // cross with the axis least aligned with the vector, to avoid a vanishing product
+        let Vector(x, y, z) = *self;
+        let axis = if x.abs() <= y.abs() && x.abs() <= z.abs() {
+            Vector(1.0, 0.0, 0.0)
+        } else if y.abs() <= z.abs() {
+            Vector(0.0, 1.0, 0.0)
+        } else {
+            Vector(0.0, 0.0, 1.0)
+        };
+        let perpendicular = Vector::vectoric_product(self, &axis);
+        if tolerance::is_zero(perpendicular.length()) {
+            panic!("The zero vector has no perpendicular direction")
+        }
+        (1.0 / perpendicular.length()) * perpendicular
+    }
+
+    /// Orthonormalize vectors with the Gram–Schmidt process, in order.
+    /// Vectors linearly dependent on the previous ones are skipped, so at most three vectors are returned
+    pub fn gram_schmidt(vectors: &[Vector]) -> Vec<Vector> {
+        let mut basis: Vec<Vector> = vec![];
+        for vector in vectors {
+            // subtract the components along the basis so far, one at a time for numerical stability
+            let rest = basis.iter().fold(*vector, |rest, axis| rest - (rest * *axis) * *axis);
+            // compare relative to the original length, so the test doesn't depend on the scale
+            if !tolerance::is_zero(vector.length()) && !tolerance::is_zero(rest.length() / vector.length()) {
+                basis.push((1.0 / rest.length()) * rest);
+            }
+        }
+        basis
     }
 
     /// Compute the angle between two vectors, in radians
@@ -58,6 +118,15 @@ impl Mul for Vector {
     }
 }
 
+impl Neg for Vector {
+    type Output = Self;
+
+    /// The opposite vector, with the same length
+    fn neg(self) -> Self::Output {
+        Vector(-self.0, -self.1, -self.2)
+    }
+}
+
 /// multiply a vector by a scalar
 impl Mul<Vector> for f64 {
     type Output = Vector;
@@ -101,6 +170,7 @@ mod tests {
     use std::f64::consts::PI;
 
     use super::*;
+    use crate::test_util::EPSILON;
 
     #[test]
     fn sum_works() {
@@ -120,6 +190,11 @@ mod tests {
         assert_eq!(Vector(0.0, 0.0, 1.0) * Vector(0.0, 1.0, 1.0), 1.0);
     }
 
+    #[test]
+    fn negate_works() {
+        assert_eq!(-Vector(1.0, -2.0, 0.0), Vector(-1.0, 2.0, 0.0));
+    }
+
     #[test]
     fn multiply_by_scalar() {
         assert_eq!(2.0 * Vector(1.0, 2.0, 3.0), Vector(2.0, 4.0, 6.0));
@@ -144,6 +219,49 @@ mod tests {
         assert!(Vector(0.0, 0.0, 1.0).is_lindep(&Vector(0.0, 0.0, 2.0)));
         assert!(!Vector(0.0, 0.0, 1.0).is_lindep(&Vector(0.0, 1.0, 2.0)));
         assert!(Vector(2.0, 2.0, 1.0).is_lindep(&Vector(4.0, 4.0, 2.0)));
+        // off parallel only by rounding
+        assert!(Vector(0.1 + 0.2, 0.3, 0.0).is_lindep(&Vector(1.0, 1.0, 0.0)));
+        assert!(Vector(1e-6, 0.0, 0.0).is_lindep(&Vector(0.0, 0.0, 0.0)));
+        assert!(!Vector(1e-6, 0.0, 0.0).is_lindep(&Vector(0.0, 1e-6, 0.0)));
+    }
+
+    #[test]
+    fn triple_dependency_works() {
+        let (x, y, z) = (Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        assert!(!Vector::are_lindep(&x, &y, &z));
+        assert!(Vector::are_lindep(&x, &y, &Vector(3.0, -2.0, 0.0)));
+        assert!(Vector::are_lindep(&x, &y, &Vector(0.0, 0.0, 0.0)));
+        assert!(Vector::are_lindep(&Vector(1.0, 2.0, 3.0), &Vector(2.0, 4.0, 6.0), &z));
+        // short vectors are only dependent by their directions
+        assert!(!Vector::are_lindep(&(1e-4 * x), &(1e-4 * y), &(1e-4 * z)));
+    }
+
+    #[test]
+    fn decomposition_works() {
+        let (u, v, w) = (Vector(1.0, 1.0, 0.0), Vector(0.0, 1.0, 1.0), Vector(1.0, 0.0, 1.0));
+        let target = 2.0 * u + (-1.0) * v + 3.0 * w;
+        assert_eq!(target.decompose(&u, &v, &w).unwrap(), (2.0, -1.0, 3.0));
+        assert!(target.decompose(&u, &v, &(u + v)).is_err());
+    }
+
+    #[test]
+    fn perpendicular_works() {
+        for vector in [Vector(1.0, 0.0, 0.0), Vector(0.0, 0.0, -3.0), Vector(1.0, 2.0, 3.0)] {
+            let perpendicular = vector.any_perpendicular();
+            assert_eq!(perpendicular * vector, 0.0);
+            assert!((perpendicular.length() - 1.0).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn gram_schmidt_works() {
+        let vectors = [Vector(1.0, 1.0, 0.0), Vector(2.0, 2.0, 0.0), Vector(1.0, 0.0, 0.0), Vector(3.0, -1.0, 2.0), Vector(1.0, 2.0, 3.0)];
+        let basis = Vector::gram_schmidt(&vectors);
+        assert_eq!(basis.len(), 3);
+        assert!((basis[0] - (1.0 / 2.0_f64.sqrt()) * Vector(1.0, 1.0, 0.0)).length() < EPSILON);
+        assert!((basis[1] - (1.0 / 2.0_f64.sqrt()) * Vector(1.0, -1.0, 0.0)).length() < EPSILON);
+        assert!((basis[2] - Vector(0.0, 0.0, 1.0)).length() < EPSILON);
+        assert!(Vector::gram_schmidt(&[Vector(0.0, 0.0, 0.0)]).is_empty());
     }
 
     #[test]
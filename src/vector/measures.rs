@@ -0,0 +1,83 @@
+//! Areas and volumes spanned by vectors and points
+
+use super::Vector;
+
+impl Vector {
+    /// Compute the scalar triple product `(u x v) * w`, the determinant of the three vectors
+    pub fn triple_product(u: &Vector, v: &Vector, w: &Vector) -> f64 {
+        Vector::vectoric_product(u, v) * (*w)
+    }
+
+    /// Compute the signed volume of the parallelepiped spanned by three vectors.
+    /// The volume is positive when the vectors form a right-handed system
+    pub fn parallelepiped_volume(u: &Vector, v: &Vector, w: &Vector) -> f64 {
+        Vector::triple_product(u, v, w)
+    }
+
+    /// Compute the signed volume of the tetrahedron spanned by three vectors from a common vertex
+    pub fn tetrahedron_volume(u: &Vector, v: &Vector, w: &Vector) -> f64 {
+        Vector::triple_product(u, v, w) / 6.0
+    }
+
+    /// Compute the signed volume of the tetrahedron with the given vertices.
+    /// The volume is positive when the edges from the first vertex form a right-handed system
+    pub fn tetrahedron_volume_of_points(point1: &Vector, point2: &Vector, point3: &Vector, point4: &Vector) -> f64 {
+        Vector::tetrahedron_volume(&(*point2 - *point1), &(*point3 - *point1), &(*point4 - *point1))
+    }
+
+    /// Compute the area of the parallelogram spanned by two vectors
+    pub fn parallelogram_area(u: &Vector, v: &Vector) -> f64 {
+        Vector::vectoric_product(u, v).length()
+    }
+
+    /// Compute the area of the parallelogram with three given consecutive vertices
+    pub fn parallelogram_area_of_points(point1: &Vector, point2: &Vector, point3: &Vector) -> f64 {
+        Vector::parallelogram_area(&(*point1 - *point2), &(*point3 - *point2))
+    }
+
+    /// Compute the area of the triangle spanned by two vectors from a common vertex
+    pub fn triangle_area(u: &Vector, v: &Vector) -> f64 {
+        Vector::parallelogram_area(u, v) / 2.0
+    }
+
+    /// Compute the area of the triangle with the given vertices
+    pub fn triangle_area_of_points(point1: &Vector, point2: &Vector, point3: &Vector) -> f64 {
+        Vector::triangle_area(&(*point2 - *point1), &(*point3 - *point1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triple_product_works() {
+        let (x, y, z) = (Vector(1.0, 0.0, 0.0), Vector(0.0, 1.0, 0.0), Vector(0.0, 0.0, 1.0));
+        assert_eq!(Vector::triple_product(&x, &y, &z), 1.0);
+        assert_eq!(Vector::triple_product(&y, &x, &z), -1.0);
+        assert_eq!(Vector::triple_product(&x, &y, &Vector(1.0, 1.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn volumes() {
+        let (u, v, w) = (Vector(2.0, 0.0, 0.0), Vector(0.0, 3.0, 0.0), Vector(1.0, 1.0, 4.0));
+        assert_eq!(Vector::parallelepiped_volume(&u, &v, &w), 24.0);
+        assert_eq!(Vector::tetrahedron_volume(&u, &v, &w), 4.0);
+        assert_eq!(Vector::tetrahedron_volume(&v, &u, &w), -4.0);
+
+        let origin = Vector(1.0, 1.0, 1.0);
+        let volume = Vector::tetrahedron_volume_of_points(&origin, &(origin + u), &(origin + v), &(origin + w));
+        assert_eq!(volume, 4.0);
+    }
+
+    #[test]
+    fn areas() {
+        let (u, v) = (Vector(3.0, 0.0, 0.0), Vector(1.0, 2.0, 0.0));
+        assert_eq!(Vector::parallelogram_area(&u, &v), 6.0);
+        assert_eq!(Vector::triangle_area(&u, &v), 3.0);
+
+        let (p1, p2, p3) = (Vector(0.0, 0.0, 1.0), Vector(0.0, 4.0, 1.0), Vector(3.0, 4.0, 1.0));
+        assert_eq!(Vector::triangle_area_of_points(&p1, &p2, &p3), 6.0);
+        assert_eq!(Vector::parallelogram_area_of_points(&p1, &p2, &p3), 12.0);
+    }
+}